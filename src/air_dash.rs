@@ -0,0 +1,110 @@
+use crate::vars::PLAYER_MAX;
+
+/// Which directions an air dash can go in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AirDashDirections {
+    Forward,
+    ForwardBack,
+    EightWay
+}
+
+/// Per-fighter settings for air dashes. Set through FGCModule::set_air_dash_config.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AirDashConfig {
+    pub enabled: bool,
+    pub directions: AirDashDirections,
+    /// The speed on the first frame of the air dash.
+    pub speed_start: f32,
+    /// The speed on the last frame of the air dash. The speed in between goes linearly from speed_start to speed_end.
+    pub speed_end: f32,
+    /// How many frames the air dash lasts.
+    pub duration: f32,
+    /// How many air dashes you get before landing.
+    pub count_max: i32,
+    /// How far from the floor you need to be to air dash.
+    pub min_height: f32,
+    /// The frame the air dash can be cancelled into an aerial.
+    pub aerial_cancel_frame: f32
+}
+
+impl AirDashConfig {
+    pub const DISABLED : AirDashConfig = AirDashConfig {
+        enabled: false,
+        directions: AirDashDirections::ForwardBack,
+        speed_start: 3.0,
+        speed_end: 1.0,
+        duration: 20.0,
+        count_max: 1,
+        min_height: 0.0,
+        aerial_cancel_frame: 10.0
+    };
+
+    /// Returns the speed of the air dash on a given frame.
+    pub fn speed_at(&self, frame: f32) -> f32 {
+        if self.duration <= 0.0 {
+            return self.speed_end;
+        }
+        let ratio = (frame / self.duration).clamp(0.0, 1.0);
+        self.speed_start + ((self.speed_end - self.speed_start) * ratio)
+    }
+
+    /// Turns a numpad direction (relative to the way the fighter is facing) into the direction of the air dash.
+    /// Neutral goes forward. Returns None if the direction isn't allowed.
+    pub fn direction(&self, dir: i32) -> Option<(f32, f32)> {
+        let dir = if dir == 5 { 6 } else { dir };
+        let x = ((dir - 1) % 3 - 1) as f32;
+        let y = ((dir - 1) / 3 - 1) as f32;
+        let allowed = match self.directions {
+            AirDashDirections::Forward => dir == 6,
+            AirDashDirections::ForwardBack => dir == 4 || dir == 6,
+            AirDashDirections::EightWay => (1..=9).contains(&dir)
+        };
+        if !allowed {
+            return None;
+        }
+        let length = (x * x + y * y).sqrt();
+        Some((x / length, y / length))
+    }
+}
+
+impl Default for AirDashConfig {
+    fn default() -> Self {
+        AirDashConfig::DISABLED
+    }
+}
+
+/// Air dash settings for each player.
+pub static mut AIR_DASH_CONFIGS : [AirDashConfig; PLAYER_MAX] = [AirDashConfig::DISABLED; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_goes_from_start_to_end() {
+        let config = AirDashConfig::DISABLED;
+        assert_eq!(config.speed_at(0.0), 3.0);
+        assert_eq!(config.speed_at(10.0), 2.0);
+        assert_eq!(config.speed_at(20.0), 1.0);
+        assert_eq!(config.speed_at(40.0), 1.0);
+    }
+
+    #[test]
+    fn directions_are_limited() {
+        let config = AirDashConfig { directions: AirDashDirections::Forward, ..AirDashConfig::DISABLED };
+        assert_eq!(config.direction(5), Some((1.0, 0.0)));
+        assert_eq!(config.direction(4), None);
+        let config = AirDashConfig::DISABLED;
+        assert_eq!(config.direction(4), Some((-1.0, 0.0)));
+        assert_eq!(config.direction(9), None);
+    }
+
+    #[test]
+    fn eight_way_diagonals_are_normalized() {
+        let config = AirDashConfig { directions: AirDashDirections::EightWay, ..AirDashConfig::DISABLED };
+        let (x, y) = config.direction(9).unwrap();
+        assert!((x - y).abs() < 0.0001);
+        assert!(((x * x + y * y) - 1.0).abs() < 0.0001);
+        assert_eq!(config.direction(2), Some((0.0, -1.0)));
+    }
+}
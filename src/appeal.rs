@@ -0,0 +1,108 @@
+use crate::vars::PLAYER_MAX;
+
+/// How a taunt behaves while its button is held.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AppealKind {
+    /// Plays once, like a vanilla taunt.
+    Normal,
+    /// Pauses on the restart frame for as long as the button is held.
+    Hold,
+    /// Loops loop_mot for as long as the button is held.
+    Loop
+}
+
+/// A buff a taunt gives once it reaches buff_frame.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AppealBuff {
+    None,
+    /// Adds meter, the same way as FGCModule::update_meter.
+    Meter { amount: f32, meter_max: f32, meter_const: i32 },
+    /// Turns on an install flag, and sets a timer for how long it lasts.
+    Install { flag: i32, timer_const: i32, frames: f32 }
+}
+
+/// Describes one of a fighter's taunts. Set through MiscModule::set_appeals.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AppealDef {
+    /// The motion kind of the taunt, such as hash40("appeal_hi_r").
+    pub motion: u64,
+    pub kind: AppealKind,
+    /// The motion kind that plays while a Loop taunt is held.
+    pub loop_mot: u64,
+    /// The frame a Hold or Loop taunt returns to.
+    pub restart_frame: i32,
+    /// The button (*CONTROL_PAD_BUTTON_XXXXXX) that keeps a Hold or Loop taunt going.
+    pub held_button: i32,
+    /// The motion kind the taunt can be cancelled into by pressing action_button, or 0 for none.
+    pub action_mot: u64,
+    /// The button (*CONTROL_PAD_BUTTON_XXXXXX) that cancels the taunt into action_mot.
+    pub action_button: i32,
+    pub buff: AppealBuff,
+    /// The frame of the taunt the buff is given on.
+    pub buff_frame: f32
+}
+
+impl AppealDef {
+    /// Returns the buff to give on this frame of the taunt, if it's reached buff_frame and hasn't been given yet.
+    pub fn buff_due(&self, frame: f32, given: bool) -> Option<AppealBuff> {
+        if given || frame < self.buff_frame {
+            return None;
+        }
+        Some(self.buff)
+    }
+}
+
+/// Finds the taunt that uses the given motion kind.
+pub fn find_appeal(appeals: &[AppealDef], motion: u64) -> Option<&AppealDef> {
+    appeals.iter().find(|appeal| appeal.motion == motion)
+}
+
+// Vec isn't Copy, so the array repeat expression below needs a const to build each player's list from.
+const NO_APPEALS : Vec<AppealDef> = Vec::new();
+
+/// The taunts for each player.
+pub static mut APPEALS : [Vec<AppealDef>; PLAYER_MAX] = [NO_APPEALS; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METER : AppealBuff = AppealBuff::Meter { amount: 10.0, meter_max: 100.0, meter_const: 0 };
+
+    fn appeal(motion: u64, kind: AppealKind) -> AppealDef {
+        AppealDef {
+            motion,
+            kind,
+            loop_mot: 0,
+            restart_frame: 0,
+            held_button: 0,
+            action_mot: 0,
+            action_button: 0,
+            buff: METER,
+            buff_frame: 20.0
+        }
+    }
+
+    #[test]
+    fn appeals_are_found_by_motion() {
+        let appeals = [appeal(1, AppealKind::Normal), appeal(2, AppealKind::Hold), appeal(3, AppealKind::Loop)];
+        assert_eq!(find_appeal(&appeals, 2).map(|appeal| appeal.kind), Some(AppealKind::Hold));
+        assert_eq!(find_appeal(&appeals, 3).map(|appeal| appeal.kind), Some(AppealKind::Loop));
+        assert_eq!(find_appeal(&appeals, 4), None);
+        assert_eq!(find_appeal(&[], 1), None);
+    }
+
+    #[test]
+    fn buffs_wait_for_buff_frame() {
+        let appeal = appeal(1, AppealKind::Normal);
+        assert_eq!(appeal.buff_due(19.0, false), None);
+        assert_eq!(appeal.buff_due(20.0, false), Some(METER));
+        assert_eq!(appeal.buff_due(35.0, false), Some(METER));
+    }
+
+    #[test]
+    fn buffs_are_only_given_once() {
+        let appeal = appeal(1, AppealKind::Normal);
+        assert_eq!(appeal.buff_due(20.0, true), None);
+    }
+}
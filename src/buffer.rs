@@ -0,0 +1,148 @@
+use crate::vars::PLAYER_MAX;
+
+/// Used for cat1 bits that haven't been pressed recently, or that have been consumed.
+const AGE_NONE : u8 = u8::MAX;
+
+/// Tracks how many frames ago each cat1 flag was last pressed, so cancels can accept inputs
+/// that were made a few frames before the cancel window opened.
+/// Only presses made since the current status began are kept, so the input that started a move
+/// can't be reused to cancel out of it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InputBuffer {
+    ages: [u8; 32],
+    held: i32,
+    status: i32,
+    /// How many frames an input stays buffered for. 0 only accepts inputs on the current frame.
+    pub window: u8
+}
+
+impl InputBuffer {
+    pub const NEW : InputBuffer = InputBuffer {
+        ages: [AGE_NONE; 32],
+        held: 0,
+        status: -1,
+        window: 5
+    };
+
+    /// Records this frame's cat1 flags. Should be called exactly once per frame, since every call ages the buffer.
+    /// Buffered inputs don't age during hitlag, so anything pressed during hitlag
+    /// is still available once it ends.
+    /// # Arguments
+    ///
+    /// * `status` - The current status kind.
+    /// * `status_changing` - If a new status is starting this frame, which catches changing into the same status again.
+    ///   The buffer is cleared whenever a new status starts, and flags that are already on at that point
+    ///   don't count until they're pressed again.
+    pub fn update(&mut self, cat1: i32, in_hitlag: bool, status: i32, status_changing: bool) {
+        if status != self.status
+        || status_changing {
+            self.status = status;
+            self.ages = [AGE_NONE; 32];
+            self.held = cat1;
+            return;
+        }
+        let pressed = cat1 & !self.held;
+        self.held = cat1;
+        for bit in 0..32 {
+            if pressed & (1 << bit) != 0 {
+                self.ages[bit] = 0;
+            }
+            else if !in_hitlag && self.ages[bit] != AGE_NONE {
+                self.ages[bit] = self.ages[bit].saturating_add(1);
+            }
+        }
+    }
+
+    /// Checks if any of the flags in the mask were pressed within the buffer window.
+    pub fn is_buffered(&self, mask: i32) -> bool {
+        (0..32).any(|bit| mask & (1 << bit) != 0 && self.ages[bit] <= self.window)
+    }
+
+    /// Removes the flags in the mask from the buffer, so one input can't be used for two cancels.
+    pub fn consume(&mut self, mask: i32) {
+        for bit in 0..32 {
+            if mask & (1 << bit) != 0 {
+                self.ages[bit] = AGE_NONE;
+            }
+        }
+    }
+
+    /// Clears the whole buffer.
+    pub fn clear(&mut self) {
+        self.ages = [AGE_NONE; 32];
+    }
+}
+
+impl Default for InputBuffer {
+    fn default() -> Self {
+        InputBuffer::NEW
+    }
+}
+
+/// The cat1 input buffer for each player.
+pub static mut INPUT_BUFFERS : [InputBuffer; PLAYER_MAX] = [InputBuffer::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ATTACK : i32 = 1 << 3;
+
+    #[test]
+    fn inputs_expire_after_the_window() {
+        let mut buffer = InputBuffer::NEW;
+        buffer.update(0, false, 1, false);
+        buffer.update(ATTACK, false, 1, false);
+        for _ in 0..5 {
+            buffer.update(0, false, 1, false);
+        }
+        assert!(buffer.is_buffered(ATTACK));
+        buffer.update(0, false, 1, false);
+        assert!(!buffer.is_buffered(ATTACK));
+    }
+
+    #[test]
+    fn inputs_dont_age_in_hitlag() {
+        let mut buffer = InputBuffer::NEW;
+        buffer.update(0, false, 1, false);
+        buffer.update(ATTACK, true, 1, false);
+        for _ in 0..20 {
+            buffer.update(0, true, 1, false);
+        }
+        assert!(buffer.is_buffered(ATTACK));
+    }
+
+    #[test]
+    fn restarting_the_status_clears_the_buffer() {
+        let mut buffer = InputBuffer::NEW;
+        buffer.update(0, false, 1, false);
+        buffer.update(ATTACK, false, 1, false);
+        buffer.update(0, false, 1, true);
+        assert!(!buffer.is_buffered(ATTACK));
+    }
+
+    #[test]
+    fn status_change_clears_the_buffer() {
+        let mut buffer = InputBuffer::NEW;
+        buffer.update(0, false, 1, false);
+        buffer.update(ATTACK, false, 1, false);
+        buffer.update(ATTACK, false, 2, false);
+        assert!(!buffer.is_buffered(ATTACK));
+        buffer.update(ATTACK, true, 2, false);
+        assert!(!buffer.is_buffered(ATTACK));
+        buffer.update(0, false, 2, false);
+        buffer.update(ATTACK, false, 2, false);
+        assert!(buffer.is_buffered(ATTACK));
+    }
+
+    #[test]
+    fn consumed_inputs_are_gone() {
+        let mut buffer = InputBuffer::NEW;
+        buffer.update(0, false, 1, false);
+        buffer.update(ATTACK, false, 1, false);
+        buffer.consume(ATTACK);
+        assert!(!buffer.is_buffered(ATTACK));
+        buffer.update(ATTACK, false, 1, false);
+        assert!(!buffer.is_buffered(ATTACK));
+    }
+}
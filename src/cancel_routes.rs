@@ -0,0 +1,156 @@
+// The cancel route tables used by FGCModule::cancel_routes. Deciding which routes are open
+// doesn't touch the game, so it lives here where it can be tested.
+
+/// The kind of dash a dash-cancel goes into.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DashKind {
+    /// A normal dash, which can be held into a run.
+    #[default]
+    Dash,
+    /// Goes straight into a run when cancelling forward.
+    Run,
+    /// A dash that turns on FIGHTER_INSTANCE_WORK_ID_FLAG_STEP_DASH, so the dash status can stop it from becoming a run.
+    StepDash
+}
+
+/// The frames of a move's motion during which a cancel route is allowed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CancelWindow {
+    /// The first motion frame the route is allowed on.
+    pub start: f32,
+    /// The motion frame the route stops being allowed on.
+    pub end: f32
+}
+
+impl CancelWindow {
+    /// Allows the route for the whole motion.
+    pub const ALWAYS : CancelWindow = CancelWindow {
+        start: 0.0,
+        end: f32::MAX
+    };
+
+    pub const fn new(start: f32, end: f32) -> CancelWindow {
+        CancelWindow {
+            start,
+            end
+        }
+    }
+
+    /// Checks if the motion frame is inside the window.
+    pub fn contains(&self, frame: f32) -> bool {
+        self.start <= frame && frame < self.end
+    }
+}
+
+impl Default for CancelWindow {
+    fn default() -> Self {
+        CancelWindow::ALWAYS
+    }
+}
+
+/// Describes every cancel route a move has, and when each route is allowed.
+/// Each condition is a combination of CANCEL_ON_HIT, CANCEL_ON_BLOCK and CANCEL_ON_WHIFF, or 0 to disable the route.
+/// Each route also has a window of motion frames it's limited to, which is the only thing stopping whiff cancels
+/// from being allowed for the whole move.
+#[derive(Clone, Default)]
+pub struct CancelRoutes {
+    pub jump: i32,
+    pub jump_window: CancelWindow,
+    pub dash: i32,
+    pub back_dash: i32,
+    pub dash_kind: DashKind,
+    /// Used for both forward and back dashes.
+    pub dash_window: CancelWindow,
+    pub air_dash: i32,
+    pub air_dash_window: CancelWindow,
+    pub aerial: i32,
+    pub aerial_window: CancelWindow,
+    pub normals: Vec<i32>,
+    pub normal: i32,
+    pub normal_window: CancelWindow,
+    pub specials: Vec<i32>,
+    pub special: i32,
+    pub special_window: CancelWindow
+}
+
+/// Which of a CancelRoutes' routes are open on the current frame.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct OpenRoutes {
+    pub jump: bool,
+    pub dash: bool,
+    pub back_dash: bool,
+    pub air_dash: bool,
+    pub aerial: bool,
+    pub normal: bool,
+    pub special: bool
+}
+
+impl CancelRoutes {
+    /// Checks each route's condition and window.
+    /// # Arguments
+    ///
+    /// * `contact` - How the move made contact (CANCEL_ON_HIT, CANCEL_ON_BLOCK or CANCEL_ON_WHIFF), or 0 if it can't be cancelled yet.
+    /// * `air_dash_contact` - The same, but counting the contact frame itself, which air dashes are allowed on.
+    /// * `frame` - The current motion frame.
+    pub fn open_routes(&self, contact: i32, air_dash_contact: i32, frame: f32) -> OpenRoutes {
+        let allowed = |condition: i32, window: CancelWindow| condition & contact != 0 && window.contains(frame);
+        OpenRoutes {
+            jump: allowed(self.jump, self.jump_window),
+            dash: allowed(self.dash, self.dash_window),
+            back_dash: allowed(self.back_dash, self.dash_window),
+            air_dash: self.air_dash & air_dash_contact != 0 && self.air_dash_window.contains(frame),
+            aerial: allowed(self.aerial, self.aerial_window),
+            normal: allowed(self.normal, self.normal_window) && !self.normals.is_empty(),
+            special: allowed(self.special, self.special_window) && !self.specials.is_empty()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HIT : i32 = 0b001;
+    const WHIFF : i32 = 0b100;
+
+    #[test]
+    fn windows() {
+        let window = CancelWindow::new(8.0, 20.0);
+        assert!(!window.contains(7.0));
+        assert!(window.contains(8.0));
+        assert!(!window.contains(20.0));
+        assert!(CancelWindow::ALWAYS.contains(500.0));
+    }
+
+    #[test]
+    fn dash_routes_dont_open_air_dashes() {
+        let routes = CancelRoutes {
+            dash: HIT,
+            back_dash: HIT,
+            ..Default::default()
+        };
+        let open = routes.open_routes(HIT, HIT, 10.0);
+        assert!(open.dash && open.back_dash);
+        assert!(!open.air_dash);
+    }
+
+    #[test]
+    fn routes_need_their_condition_and_window() {
+        let routes = CancelRoutes {
+            jump: HIT,
+            air_dash: HIT,
+            air_dash_window: CancelWindow::new(5.0, 10.0),
+            special: HIT | WHIFF,
+            special_window: CancelWindow::new(8.0, 20.0),
+            specials: vec![1],
+            normal: HIT,
+            ..Default::default()
+        };
+        let open = routes.open_routes(WHIFF, WHIFF, 10.0);
+        assert_eq!(open, OpenRoutes { special: true, ..Default::default() });
+        let open = routes.open_routes(0, HIT, 6.0);
+        assert_eq!(open, OpenRoutes { air_dash: true, ..Default::default() });
+        let open = routes.open_routes(HIT, HIT, 25.0);
+        assert_eq!(open, OpenRoutes { jump: true, ..Default::default() });
+    }
+}
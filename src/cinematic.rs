@@ -0,0 +1,263 @@
+use crate::vars::PLAYER_MAX;
+
+/// Something a cinematic does on a given frame.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CinematicAction {
+    /// Zooms the camera in on the fighter. Skipped when there are more than two players.
+    Zoom { frames: f32, zoom: f32 },
+    /// Returns the camera to normal.
+    ZoomOut,
+    /// Slows the whole game down. A rate of 2 is half speed.
+    Slow { rate: u8, frames: i32 },
+    /// Returns the game to normal speed.
+    SlowClear,
+    /// Plays an effect on the fighter, such as hash40("sys_bg_criticalhit") to darken the background.
+    Effect { effect: u64, bone: u64, scale: f32 },
+    /// Plays a sound effect.
+    Sound { sound: u64 }
+}
+
+impl CinematicAction {
+    /// Checks if this action moves the camera, which is skipped when there are more than two players.
+    pub fn is_camera(&self) -> bool {
+        matches!(self, CinematicAction::Zoom { .. })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CinematicEvent {
+    pub frame: i32,
+    pub action: CinematicAction
+}
+
+/// A scripted sequence of camera, slowdown, effect and sound actions.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Cinematic {
+    pub events: Vec<CinematicEvent>,
+    /// How many frames the cinematic lasts before it gets torn down.
+    pub length: i32
+}
+
+/// Everything that needs to be undone when a cinematic ends or gets interrupted.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CinematicTeardown {
+    pub zoom_out: bool,
+    pub slow_clear: bool,
+    pub effects: Vec<u64>
+}
+
+/// The things that stop a cinematic early.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CinematicInterrupts {
+    /// The fighter got hit.
+    pub damaged: bool,
+    /// The fighter got KO'd.
+    pub dead: bool,
+    /// The match isn't running, such as when it's over.
+    pub not_ready_go: bool
+}
+
+impl CinematicInterrupts {
+    pub fn any(&self) -> bool {
+        self.damaged || self.dead || self.not_ready_go
+    }
+}
+
+/// What to do on one frame of a cinematic.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CinematicFrame {
+    pub actions: Vec<CinematicAction>,
+    /// What needs to be undone, once the cinematic has ended or been interrupted.
+    pub teardown: Option<CinematicTeardown>
+}
+
+/// Plays a cinematic back one frame at a time.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CinematicPlayer {
+    cinematic: Option<Cinematic>,
+    frame: i32,
+    next_event: usize,
+    teardown: CinematicTeardown
+}
+
+impl CinematicPlayer {
+    pub const NEW : CinematicPlayer = CinematicPlayer {
+        cinematic: None,
+        frame: 0,
+        next_event: 0,
+        teardown: CinematicTeardown {
+            zoom_out: false,
+            slow_clear: false,
+            effects: Vec::new()
+        }
+    };
+
+    pub fn is_playing(&self) -> bool {
+        self.cinematic.is_some()
+    }
+
+    /// Starts a cinematic. Returns the teardown of the cinematic that was already playing, if there was one.
+    pub fn start(&mut self, mut cinematic: Cinematic) -> Option<CinematicTeardown> {
+        let teardown = self.stop();
+        cinematic.events.sort_by_key(|event| event.frame);
+        self.cinematic = Some(cinematic);
+        teardown
+    }
+
+    /// Returns the actions to run on the current frame, then moves to the next frame.
+    /// # Arguments
+    ///
+    /// * `suppress_camera` - Skips camera actions. Used when there are more than two players.
+    pub fn advance(&mut self, suppress_camera: bool) -> Vec<CinematicAction> {
+        let mut actions = Vec::new();
+        let cinematic = match &self.cinematic {
+            Some(cinematic) => cinematic,
+            None => return actions
+        };
+        while let Some(event) = cinematic.events.get(self.next_event) {
+            if event.frame > self.frame {
+                break;
+            }
+            self.next_event += 1;
+            if suppress_camera && event.action.is_camera() {
+                continue;
+            }
+            match event.action {
+                CinematicAction::Zoom { .. } => self.teardown.zoom_out = true,
+                CinematicAction::ZoomOut => self.teardown.zoom_out = false,
+                CinematicAction::Slow { .. } => self.teardown.slow_clear = true,
+                CinematicAction::SlowClear => self.teardown.slow_clear = false,
+                CinematicAction::Effect { effect, .. } => self.teardown.effects.push(effect),
+                CinematicAction::Sound { .. } => {}
+            }
+            actions.push(event.action);
+        }
+        self.frame += 1;
+        actions
+    }
+
+    /// Checks if the cinematic has run for its whole length.
+    pub fn is_finished(&self) -> bool {
+        match &self.cinematic {
+            Some(cinematic) => self.frame >= cinematic.length,
+            None => false
+        }
+    }
+
+    /// Runs one frame of the cinematic. Should be called once per frame.
+    /// If anything interrupted it, the cinematic is stopped without running this frame's actions.
+    /// Otherwise the frame's actions are returned, along with the teardown if that was the last frame.
+    pub fn update(&mut self, interrupts: CinematicInterrupts, suppress_camera: bool) -> CinematicFrame {
+        if !self.is_playing() {
+            return CinematicFrame::default();
+        }
+        if interrupts.any() {
+            return CinematicFrame {
+                actions: Vec::new(),
+                teardown: self.stop()
+            };
+        }
+        let actions = self.advance(suppress_camera);
+        let teardown = if self.is_finished() { self.stop() } else { None };
+        CinematicFrame {
+            actions,
+            teardown
+        }
+    }
+
+    /// Stops the cinematic. Returns what needs to be undone, or None if nothing was playing.
+    pub fn stop(&mut self) -> Option<CinematicTeardown> {
+        self.cinematic.take()?;
+        self.frame = 0;
+        self.next_event = 0;
+        Some(std::mem::take(&mut self.teardown))
+    }
+}
+
+/// The cinematic playing for each player.
+pub static mut CINEMATICS : [CinematicPlayer; PLAYER_MAX] = [CinematicPlayer::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DARKEN : u64 = 0x1234;
+
+    fn cinematic() -> Cinematic {
+        Cinematic {
+            events: vec![
+                CinematicEvent { frame: 2, action: CinematicAction::SlowClear },
+                CinematicEvent { frame: 0, action: CinematicAction::Zoom { frames: 5.0, zoom: 1.5 } },
+                CinematicEvent { frame: 0, action: CinematicAction::Slow { rate: 2, frames: 10 } },
+                CinematicEvent { frame: 1, action: CinematicAction::Effect { effect: DARKEN, bone: 0, scale: 1.0 } }
+            ],
+            length: 4
+        }
+    }
+
+    fn started() -> CinematicPlayer {
+        let mut player = CinematicPlayer::NEW;
+        assert_eq!(player.start(cinematic()), None);
+        player
+    }
+
+    #[test]
+    fn plays_to_the_end() {
+        let mut player = started();
+        let frame = player.update(CinematicInterrupts::default(), false);
+        assert_eq!(frame.actions, [CinematicAction::Zoom { frames: 5.0, zoom: 1.5 }, CinematicAction::Slow { rate: 2, frames: 10 }]);
+        assert_eq!(frame.teardown, None);
+        let frame = player.update(CinematicInterrupts::default(), false);
+        assert_eq!(frame.actions, [CinematicAction::Effect { effect: DARKEN, bone: 0, scale: 1.0 }]);
+        let frame = player.update(CinematicInterrupts::default(), false);
+        assert_eq!(frame.actions, [CinematicAction::SlowClear]);
+        let frame = player.update(CinematicInterrupts::default(), false);
+        assert!(frame.actions.is_empty());
+        assert_eq!(frame.teardown, Some(CinematicTeardown { zoom_out: true, slow_clear: false, effects: vec![DARKEN] }));
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn suppressed_camera_needs_no_zoom_out() {
+        let mut player = started();
+        let frame = player.update(CinematicInterrupts::default(), true);
+        assert_eq!(frame.actions, [CinematicAction::Slow { rate: 2, frames: 10 }]);
+        let frame = player.update(CinematicInterrupts { dead: true, ..Default::default() }, true);
+        assert_eq!(frame.teardown, Some(CinematicTeardown { zoom_out: false, slow_clear: true, effects: Vec::new() }));
+    }
+
+    fn interrupted_by(interrupts: CinematicInterrupts) {
+        let mut player = started();
+        player.update(CinematicInterrupts::default(), false);
+        player.update(CinematicInterrupts::default(), false);
+        let frame = player.update(interrupts, false);
+        assert!(frame.actions.is_empty());
+        assert_eq!(frame.teardown, Some(CinematicTeardown { zoom_out: true, slow_clear: true, effects: vec![DARKEN] }));
+        assert!(!player.is_playing());
+        assert_eq!(player.update(CinematicInterrupts::default(), false), CinematicFrame::default());
+    }
+
+    #[test]
+    fn teardown_on_damage() {
+        interrupted_by(CinematicInterrupts { damaged: true, ..Default::default() });
+    }
+
+    #[test]
+    fn teardown_on_dead() {
+        interrupted_by(CinematicInterrupts { dead: true, ..Default::default() });
+    }
+
+    #[test]
+    fn teardown_when_not_ready_go() {
+        interrupted_by(CinematicInterrupts { not_ready_go: true, ..Default::default() });
+    }
+
+    #[test]
+    fn restarting_tears_down_the_old_one() {
+        let mut player = started();
+        player.update(CinematicInterrupts::default(), false);
+        let teardown = player.start(cinematic());
+        assert_eq!(teardown, Some(CinematicTeardown { zoom_out: true, slow_clear: true, effects: Vec::new() }));
+        assert!(player.is_playing());
+    }
+}
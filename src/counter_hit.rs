@@ -0,0 +1,338 @@
+use crate::vars::PLAYER_MAX;
+
+/// Which part of an attack a fighter is in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttackPhase {
+    /// Not attacking.
+    Neutral,
+    /// Attacking, but no hitbox has come out yet.
+    Startup,
+    /// A hitbox is out.
+    Active,
+    /// The hitboxes are gone, but the attack hasn't ended yet.
+    Recovery
+}
+
+/// How a hit is classified, based on what the victim was doing when they got hit.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HitKind {
+    Normal,
+    /// The victim was hit during the startup of an attack.
+    CounterHit,
+    /// The victim was hit during the recovery of an attack.
+    PunishCounter
+}
+
+impl HitKind {
+    pub fn from_phase(phase: AttackPhase) -> HitKind {
+        match phase {
+            AttackPhase::Startup => HitKind::CounterHit,
+            AttackPhase::Recovery => HitKind::PunishCounter,
+            _ => HitKind::Normal
+        }
+    }
+}
+
+/// Keeps track of which part of an attack a fighter is in, so a hit can be classified once it lands.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PhaseTracker {
+    status: i32,
+    phase: AttackPhase,
+    has_been_active: bool,
+    damaged: bool
+}
+
+impl PhaseTracker {
+    pub const NEW : PhaseTracker = PhaseTracker {
+        status: -1,
+        phase: AttackPhase::Neutral,
+        has_been_active: false,
+        damaged: false
+    };
+
+    /// Updates the phase for this frame. Should be called once per frame.
+    /// While `damaged` is true, the phase is left as it was before the hit.
+    /// Returns how the hit was classified on the first frame of being damaged.
+    /// # Arguments
+    ///
+    /// * `is_attack_status` - If `status` is an attack.
+    /// * `attack_active` - If a hitbox is out this frame.
+    /// * `damaged` - If the fighter is being hit.
+    pub fn update(&mut self, status: i32, is_attack_status: bool, attack_active: bool, damaged: bool) -> Option<HitKind> {
+        if damaged {
+            let was_damaged = self.damaged;
+            self.damaged = true;
+            if was_damaged {
+                return None;
+            }
+            return Some(HitKind::from_phase(self.phase));
+        }
+        self.damaged = false;
+        if status != self.status {
+            self.status = status;
+            self.has_been_active = false;
+        }
+        self.phase = if !is_attack_status {
+            AttackPhase::Neutral
+        }
+        else if attack_active {
+            self.has_been_active = true;
+            AttackPhase::Active
+        }
+        else if self.has_been_active {
+            AttackPhase::Recovery
+        }
+        else {
+            AttackPhase::Startup
+        };
+        None
+    }
+
+    pub fn phase(&self) -> AttackPhase {
+        self.phase
+    }
+}
+
+impl Default for PhaseTracker {
+    fn default() -> Self {
+        PhaseTracker::NEW
+    }
+}
+
+/// Called whenever a counter-hit or punish-counter lands, with the victim's entry ID,
+/// the attacker's entry ID (if it could be found) and how the hit was classified.
+pub type HitKindListener = fn(victim: usize, attacker: Option<usize>, kind: HitKind);
+
+/// How many hit kind listeners can be added.
+pub const HIT_KIND_LISTENER_MAX : usize = 8;
+
+/// Who last hit a player, and with what.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HitRecord {
+    /// The entry ID of the fighter that landed the hit. Hits from weapons count as hits from their owner.
+    pub attacker: usize,
+    /// The attacker's motion kind (as a hash40) when the hit landed.
+    pub motion: u64
+}
+
+/// The systems that act on each recorded hit once.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HitUse {
+    /// Changing the hitstun or shieldstun from the hit (see FGCModule::stun_main).
+    Stun,
+    /// Pushing the victim away from the attacker (see FGCModule::pushback_main).
+    Pushback
+}
+
+impl HitUse {
+    fn mask(self) -> u8 {
+        match self {
+            HitUse::Stun => 0b01,
+            HitUse::Pushback => 0b10
+        }
+    }
+}
+
+/// Remembers the last hit on a player for as long as the hitstun or shieldstun from it lasts,
+/// so an old hit is never mistaken for a new one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HitLog {
+    record: Option<HitRecord>,
+    stunned: bool,
+    used: u8
+}
+
+impl HitLog {
+    pub const NEW : HitLog = HitLog {
+        record: None,
+        stunned: false,
+        used: 0
+    };
+
+    /// Records a hit. Hits that couldn't be traced back to another fighter are recorded as None,
+    /// so they aren't credited to whoever hit the player before.
+    pub fn record(&mut self, record: Option<HitRecord>) {
+        self.record = record;
+        self.used = 0;
+    }
+
+    /// Forgets the last hit once the player's stun ends. Should be called once per frame.
+    pub fn update(&mut self, stunned: bool) {
+        if self.stunned && !stunned {
+            self.record = None;
+        }
+        self.stunned = stunned;
+    }
+
+    /// Returns the hit the player is currently in stun from, if it was recorded.
+    pub fn last_hit(&self) -> Option<HitRecord> {
+        self.record
+    }
+
+    /// Returns the last hit the first time it's asked for with `hit_use` after being recorded,
+    /// so each system only acts on one hit once.
+    pub fn take(&mut self, hit_use: HitUse) -> Option<HitRecord> {
+        if self.used & hit_use.mask() != 0 {
+            return None;
+        }
+        self.used |= hit_use.mask();
+        self.record
+    }
+
+    pub fn clear(&mut self) {
+        *self = HitLog::NEW;
+    }
+}
+
+impl Default for HitLog {
+    fn default() -> Self {
+        HitLog::NEW
+    }
+}
+
+/// Finds the player that `attacker` just hit, from each player's hit log. Each hit is only found once.
+pub fn take_victim(logs: &mut [HitLog], attacker: usize) -> Option<usize> {
+    logs.iter_mut().position(|log| {
+        log.last_hit().map(|hit| hit.attacker) == Some(attacker)
+        && log.take(HitUse::Pushback).is_some()
+    })
+}
+
+/// Puts a listener in the first empty slot. Returns false if every slot is taken.
+pub fn add_listener<T>(slots: &mut [Option<T>], listener: T) -> bool {
+    match slots.iter_mut().find(|slot| slot.is_none()) {
+        Some(slot) => {
+            *slot = Some(listener);
+            true
+        },
+        None => false
+    }
+}
+
+/// The attack phase tracker for each player.
+pub static mut PHASE_TRACKERS : [PhaseTracker; PLAYER_MAX] = [PhaseTracker::NEW; PLAYER_MAX];
+
+/// How the last hit on each player was classified.
+pub static mut LAST_HIT_KINDS : [HitKind; PLAYER_MAX] = [HitKind::Normal; PLAYER_MAX];
+
+/// The last hit on each player.
+pub static mut HIT_LOGS : [HitLog; PLAYER_MAX] = [HitLog::NEW; PLAYER_MAX];
+
+/// Everything listening for counter-hits and punish-counters.
+pub static mut HIT_KIND_LISTENERS : [Option<HitKindListener>; HIT_KIND_LISTENER_MAX] = [None; HIT_KIND_LISTENER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JAB : i32 = 1;
+    const WAIT : i32 = 2;
+
+    #[test]
+    fn startup_hits_are_counter_hits() {
+        let mut tracker = PhaseTracker::NEW;
+        tracker.update(JAB, true, false, false);
+        assert_eq!(tracker.phase(), AttackPhase::Startup);
+        assert_eq!(tracker.update(JAB, true, false, true), Some(HitKind::CounterHit));
+        assert_eq!(tracker.update(JAB, true, false, true), None);
+    }
+
+    #[test]
+    fn recovery_hits_are_punish_counters() {
+        let mut tracker = PhaseTracker::NEW;
+        tracker.update(JAB, true, false, false);
+        tracker.update(JAB, true, true, false);
+        assert_eq!(tracker.phase(), AttackPhase::Active);
+        tracker.update(JAB, true, false, false);
+        assert_eq!(tracker.phase(), AttackPhase::Recovery);
+        assert_eq!(tracker.update(JAB, true, false, true), Some(HitKind::PunishCounter));
+    }
+
+    #[test]
+    fn new_statuses_start_in_startup() {
+        let mut tracker = PhaseTracker::NEW;
+        tracker.update(JAB, true, true, false);
+        tracker.update(JAB, true, false, false);
+        tracker.update(WAIT, false, false, false);
+        assert_eq!(tracker.update(WAIT, false, false, true), Some(HitKind::Normal));
+        tracker.update(JAB, true, false, false);
+        assert_eq!(tracker.phase(), AttackPhase::Startup);
+    }
+
+    const HIT : HitRecord = HitRecord { attacker: 1, motion: 100 };
+
+    #[test]
+    fn hits_are_forgotten_when_stun_ends() {
+        let mut log = HitLog::NEW;
+        log.record(Some(HIT));
+        log.update(true);
+        log.update(true);
+        assert_eq!(log.last_hit(), Some(HIT));
+        log.update(false);
+        assert_eq!(log.last_hit(), None);
+    }
+
+    #[test]
+    fn hits_recorded_before_stun_starts_are_kept() {
+        let mut log = HitLog::NEW;
+        log.update(false);
+        log.record(Some(HIT));
+        log.update(false);
+        assert_eq!(log.last_hit(), Some(HIT));
+        log.update(true);
+        assert_eq!(log.last_hit(), Some(HIT));
+    }
+
+    #[test]
+    fn untraced_hits_replace_the_last_attacker() {
+        let mut log = HitLog::NEW;
+        log.record(Some(HIT));
+        log.update(true);
+        log.record(None);
+        assert_eq!(log.last_hit(), None);
+    }
+
+    #[test]
+    fn stun_is_only_changed_once_per_hit() {
+        let mut log = HitLog::NEW;
+        log.record(Some(HIT));
+        assert_eq!(log.take(HitUse::Stun), Some(HIT));
+        assert_eq!(log.take(HitUse::Stun), None);
+        assert_eq!(log.last_hit(), Some(HIT));
+        let next = HitRecord { motion: 200, ..HIT };
+        log.record(Some(next));
+        assert_eq!(log.take(HitUse::Stun), Some(next));
+    }
+
+    #[test]
+    fn each_use_takes_the_hit_separately() {
+        let mut log = HitLog::NEW;
+        log.record(Some(HIT));
+        assert_eq!(log.take(HitUse::Stun), Some(HIT));
+        assert_eq!(log.take(HitUse::Pushback), Some(HIT));
+        assert_eq!(log.take(HitUse::Pushback), None);
+    }
+
+    #[test]
+    fn victims_are_found_from_the_hit_logs() {
+        let mut logs = [HitLog::NEW; 3];
+        logs[0].record(Some(HitRecord { attacker: 2, ..HIT }));
+        logs[2].record(Some(HIT));
+        assert_eq!(take_victim(&mut logs, 1), Some(2));
+        assert_eq!(take_victim(&mut logs, 1), None);
+        assert_eq!(take_victim(&mut logs, 2), Some(0));
+        // Checking for one attacker doesn't use up a hit from another.
+        logs[1].record(Some(HitRecord { attacker: 0, ..HIT }));
+        assert_eq!(take_victim(&mut logs, 2), None);
+        assert_eq!(take_victim(&mut logs, 0), Some(1));
+    }
+
+    #[test]
+    fn listener_slots_fill_up() {
+        let mut slots : [Option<i32>; 2] = [None; 2];
+        assert!(add_listener(&mut slots, 1));
+        assert!(add_listener(&mut slots, 2));
+        assert!(!add_listener(&mut slots, 3));
+        assert_eq!(slots, [Some(1), Some(2)]);
+    }
+}
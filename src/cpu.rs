@@ -0,0 +1,274 @@
+use crate::vars::PLAYER_MAX;
+
+/// A small xorshift random number generator, so CPU decisions are the same every time for a given seed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CpuRng {
+    state: u32
+}
+
+impl CpuRng {
+    pub const fn new(seed: u32) -> CpuRng {
+        CpuRng {
+            state: if seed == 0 { 0x9E3779B9 } else { seed }
+        }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Returns a number from 0.0 up to (but not including) 1.0.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Returns true `rate` of the time, where rate goes from 0.0 to 1.0.
+    pub fn chance(&mut self, rate: f32) -> bool {
+        self.next_f32() < rate
+    }
+
+    /// Picks a number from 0 up to (but not including) count.
+    pub fn pick(&mut self, count: usize) -> usize {
+        (self.next_u32() as usize) % count.max(1)
+    }
+}
+
+/// How well a CPU plays in Fighting Game Mode.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CpuDifficulty {
+    /// How many frames it takes the CPU to react to a cancel window or an incoming attack.
+    pub reaction_delay: i32,
+    /// How often (0.0 to 1.0) the CPU drops a combo by skipping a cancel.
+    pub drop_rate: f32,
+    /// How often (0.0 to 1.0) the CPU blocks an incoming attack.
+    pub block_rate: f32,
+    /// How likely (0.0 to 1.0) the CPU is to go for a mixup each time it gets the chance.
+    pub mixup_rate: f32
+}
+
+impl CpuDifficulty {
+    pub const EASY : CpuDifficulty = CpuDifficulty {
+        reaction_delay: 15,
+        drop_rate: 0.5,
+        block_rate: 0.2,
+        mixup_rate: 0.01
+    };
+
+    pub const NORMAL : CpuDifficulty = CpuDifficulty {
+        reaction_delay: 8,
+        drop_rate: 0.2,
+        block_rate: 0.5,
+        mixup_rate: 0.03
+    };
+
+    pub const HARD : CpuDifficulty = CpuDifficulty {
+        reaction_delay: 3,
+        drop_rate: 0.05,
+        block_rate: 0.8,
+        mixup_rate: 0.06
+    };
+}
+
+impl Default for CpuDifficulty {
+    fn default() -> Self {
+        CpuDifficulty::NORMAL
+    }
+}
+
+/// Makes a CPU's decisions for combos, blocking and mixups. Every decision comes from its CpuRng,
+/// so the same seed and the same situation always lead to the same choices.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CpuBrain {
+    rng: CpuRng,
+    pub difficulty: CpuDifficulty,
+    /// The status kinds the CPU cancels into, in order, when it gets a cancel window.
+    pub combo: Vec<i32>,
+    combo_step: usize,
+    cancel_timer: i32,
+    dropped: bool,
+    guard_timer: i32,
+    will_guard: bool
+}
+
+impl CpuBrain {
+    pub const NEW : CpuBrain = CpuBrain {
+        rng: CpuRng::new(0),
+        difficulty: CpuDifficulty::NORMAL,
+        combo: Vec::new(),
+        combo_step: 0,
+        cancel_timer: -1,
+        dropped: false,
+        guard_timer: -1,
+        will_guard: false
+    };
+
+    /// Restarts the random number generator from a new seed.
+    pub fn seed(&mut self, seed: u32) {
+        self.rng = CpuRng::new(seed);
+    }
+
+    /// Goes back to the start of the combo.
+    pub fn reset_combo(&mut self) {
+        self.combo_step = 0;
+        self.cancel_timer = -1;
+        self.dropped = false;
+    }
+
+    /// Decides if the CPU cancels into the next part of its combo this frame. Should be called once per frame.
+    /// When a cancel window opens, the CPU rolls whether it drops the combo, then waits reaction_delay frames.
+    /// Returns the status kind to cancel into, if the CPU cancels this frame.
+    pub fn combo_step(&mut self, in_window: bool) -> Option<i32> {
+        if !in_window {
+            self.cancel_timer = -1;
+            return None;
+        }
+        if self.cancel_timer < 0 {
+            self.cancel_timer = self.difficulty.reaction_delay.max(0);
+            self.dropped = self.rng.chance(self.difficulty.drop_rate);
+        }
+        if self.dropped || self.combo_step >= self.combo.len() {
+            return None;
+        }
+        if self.cancel_timer > 0 {
+            self.cancel_timer -= 1;
+            return None;
+        }
+        let status = self.combo[self.combo_step];
+        self.combo_step += 1;
+        self.cancel_timer = -1;
+        Some(status)
+    }
+
+    /// Decides if the CPU blocks this frame. Should be called once per frame.
+    /// When an attack starts, the CPU rolls whether it blocks it, then waits reaction_delay frames.
+    pub fn guard_step(&mut self, threatened: bool) -> bool {
+        if !threatened {
+            self.guard_timer = -1;
+            return false;
+        }
+        if self.guard_timer < 0 {
+            self.guard_timer = self.difficulty.reaction_delay.max(0);
+            self.will_guard = self.rng.chance(self.difficulty.block_rate);
+        }
+        if self.guard_timer > 0 {
+            self.guard_timer -= 1;
+            return false;
+        }
+        self.will_guard
+    }
+
+    /// Decides if the CPU goes for a mixup this frame, and which of the options it picks.
+    pub fn mixup_step(&mut self, option_count: usize) -> Option<usize> {
+        if option_count == 0
+        || !self.rng.chance(self.difficulty.mixup_rate) {
+            return None;
+        }
+        Some(self.rng.pick(option_count))
+    }
+}
+
+impl Default for CpuBrain {
+    fn default() -> Self {
+        CpuBrain::NEW
+    }
+}
+
+/// The CPU brain for each player.
+pub static mut CPU_BRAINS : [CpuBrain; PLAYER_MAX] = [CpuBrain::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_brain(seed: u32, difficulty: CpuDifficulty) -> CpuBrain {
+        let mut brain = CpuBrain { difficulty, combo: vec![10, 20, 30], ..CpuBrain::NEW };
+        brain.seed(seed);
+        brain
+    }
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = CpuRng::new(1234);
+        let mut b = CpuRng::new(1234);
+        let a_values: Vec<u32> = (0..100).map(|_| a.next_u32()).collect();
+        let b_values: Vec<u32> = (0..100).map(|_| b.next_u32()).collect();
+        assert_eq!(a_values, b_values);
+        let mut c = CpuRng::new(4321);
+        assert_ne!(a_values[0], c.next_u32());
+    }
+
+    #[test]
+    fn rng_zero_seed_still_works() {
+        let mut rng = CpuRng::new(0);
+        assert_eq!(rng, CpuRng::new(0x9E3779B9));
+        assert_ne!(rng.next_u32(), 0);
+    }
+
+    #[test]
+    fn rng_ranges() {
+        let mut rng = CpuRng::new(99);
+        for _ in 0..1000 {
+            let value = rng.next_f32();
+            assert!((0.0..1.0).contains(&value));
+            assert!(rng.pick(3) < 3);
+            assert_eq!(rng.pick(0), 0);
+        }
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+
+    #[test]
+    fn combo_waits_for_reaction_delay() {
+        let difficulty = CpuDifficulty { reaction_delay: 2, drop_rate: 0.0, ..CpuDifficulty::NORMAL };
+        let mut brain = new_brain(7, difficulty);
+        assert_eq!(brain.combo_step(true), None);
+        assert_eq!(brain.combo_step(true), None);
+        assert_eq!(brain.combo_step(true), Some(10));
+        assert_eq!(brain.combo_step(false), None);
+        assert_eq!(brain.combo_step(true), None);
+        assert_eq!(brain.combo_step(true), None);
+        assert_eq!(brain.combo_step(true), Some(20));
+        brain.reset_combo();
+        let steps: Vec<Option<i32>> = (0..3).map(|_| brain.combo_step(true)).collect();
+        assert_eq!(steps, [None, None, Some(10)]);
+    }
+
+    #[test]
+    fn combo_drops_at_full_drop_rate() {
+        let difficulty = CpuDifficulty { reaction_delay: 0, drop_rate: 1.0, ..CpuDifficulty::NORMAL };
+        let mut brain = new_brain(7, difficulty);
+        assert!((0..10).all(|_| brain.combo_step(true).is_none()));
+    }
+
+    #[test]
+    fn same_seed_same_decisions() {
+        let run = |seed: u32| {
+            let mut brain = new_brain(seed, CpuDifficulty::NORMAL);
+            let mut decisions = Vec::new();
+            for frame in 0..300 {
+                let threatened = frame % 40 < 20;
+                decisions.push((brain.guard_step(threatened), brain.mixup_step(3), brain.combo_step(frame % 30 < 15)));
+            }
+            decisions
+        };
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+
+    #[test]
+    fn guard_follows_block_rate() {
+        let difficulty = CpuDifficulty { reaction_delay: 1, block_rate: 1.0, ..CpuDifficulty::NORMAL };
+        let mut brain = new_brain(3, difficulty);
+        assert!(!brain.guard_step(true));
+        assert!(brain.guard_step(true));
+        assert!(!brain.guard_step(false));
+        let difficulty = CpuDifficulty { reaction_delay: 0, block_rate: 0.0, ..CpuDifficulty::NORMAL };
+        let mut brain = new_brain(3, difficulty);
+        assert!(!brain.guard_step(true));
+    }
+}
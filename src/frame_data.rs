@@ -0,0 +1,190 @@
+use crate::vars::PLAYER_MAX;
+
+/// The measured frame data of a move.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FrameData {
+    pub fighter_kind: i32,
+    /// The motion kind (as a hash40) of the move.
+    pub motion: u64,
+    /// The first frame a hitbox is out, or 0 if the move never had one.
+    pub startup: i32,
+    /// How many frames had a hitbox out.
+    pub active: i32,
+    /// How many frames there are after the last active frame until you can act.
+    pub recovery: i32,
+    /// How many frames the move lasts before you can act.
+    pub total: i32
+}
+
+/// Watches a move frame by frame to measure its frame data.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FrameDataRecorder {
+    motion: u64,
+    frame: i32,
+    first_active: i32,
+    last_active: i32,
+    active: i32,
+    finished: bool
+}
+
+impl FrameDataRecorder {
+    pub const NEW : FrameDataRecorder = FrameDataRecorder {
+        motion: 0,
+        frame: 0,
+        first_active: 0,
+        last_active: 0,
+        active: 0,
+        finished: true
+    };
+
+    /// Records one frame of a move. Should be called once per frame, skipping frames spent in hitlag.
+    /// A new motion starts a new measurement, throwing away a move that was cancelled before it finished.
+    /// Returns the frame data once the move finishes, which is the first frame `can_act` is true.
+    /// # Arguments
+    ///
+    /// * `attack_active` - If the move has a hitbox out this frame.
+    /// * `can_act` - If the move can be cancelled normally, or has ended.
+    pub fn observe(&mut self, fighter_kind: i32, motion: u64, attack_active: bool, can_act: bool) -> Option<FrameData> {
+        if motion != self.motion {
+            *self = FrameDataRecorder::NEW;
+            self.motion = motion;
+            self.finished = false;
+        }
+        if self.finished {
+            return None;
+        }
+        self.frame += 1;
+        if can_act {
+            self.finished = true;
+            let total = self.frame - 1;
+            return Some(FrameData {
+                fighter_kind,
+                motion,
+                startup: self.first_active,
+                active: self.active,
+                recovery: if self.last_active > 0 { total - self.last_active } else { total },
+                total
+            });
+        }
+        if attack_active {
+            if self.first_active == 0 {
+                self.first_active = self.frame;
+            }
+            self.last_active = self.frame;
+            self.active += 1;
+        }
+        None
+    }
+}
+
+impl Default for FrameDataRecorder {
+    fn default() -> Self {
+        FrameDataRecorder::NEW
+    }
+}
+
+/// Frame data for every move measured so far, for every fighter.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct FrameDataTable {
+    pub entries: Vec<FrameData>
+}
+
+impl FrameDataTable {
+    pub const NEW : FrameDataTable = FrameDataTable {
+        entries: Vec::new()
+    };
+
+    /// Adds a move's frame data, replacing the previous measurement of the same move.
+    pub fn record(&mut self, data: FrameData) {
+        match self.entries.iter_mut().find(|entry| entry.fighter_kind == data.fighter_kind && entry.motion == data.motion) {
+            Some(entry) => *entry = data,
+            None => self.entries.push(data)
+        }
+    }
+
+    pub fn get(&self, fighter_kind: i32, motion: u64) -> Option<&FrameData> {
+        self.entries.iter().find(|entry| entry.fighter_kind == fighter_kind && entry.motion == motion)
+    }
+
+    /// Writes the table out as CSV, sorted by fighter kind and then by motion.
+    pub fn export(&self) -> String {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|entry| (entry.fighter_kind, entry.motion));
+        let mut csv = String::from("fighter_kind,motion,startup,active,recovery,total\n");
+        for entry in entries {
+            csv.push_str(&format!(
+                "{},{:#012x},{},{},{},{}\n",
+                entry.fighter_kind,
+                entry.motion,
+                entry.startup,
+                entry.active,
+                entry.recovery,
+                entry.total
+            ));
+        }
+        csv
+    }
+}
+
+/// The frame data recorder for each player.
+pub static mut FRAME_DATA_RECORDERS : [FrameDataRecorder; PLAYER_MAX] = [FrameDataRecorder::NEW; PLAYER_MAX];
+
+/// Frame data measured during this session, for the whole roster.
+pub static mut FRAME_DATA_TABLE : FrameDataTable = FrameDataTable::NEW;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JAB : u64 = 0x11;
+    const TILT : u64 = 0x22;
+
+    /// Plays a move with the given active frames through the recorder, finishing it on `total + 1`.
+    fn measure(recorder: &mut FrameDataRecorder, motion: u64, active: &[i32], total: i32) -> Option<FrameData> {
+        for frame in 1..=total {
+            assert_eq!(recorder.observe(1, motion, active.contains(&frame), false), None);
+        }
+        recorder.observe(1, motion, false, true)
+    }
+
+    #[test]
+    fn measures_a_move() {
+        let mut recorder = FrameDataRecorder::NEW;
+        let data = measure(&mut recorder, JAB, &[3, 4, 5], 20).unwrap();
+        assert_eq!((data.startup, data.active, data.recovery, data.total), (3, 3, 15, 20));
+        assert_eq!(recorder.observe(1, JAB, false, true), None);
+    }
+
+    #[test]
+    fn moves_without_hitboxes() {
+        let mut recorder = FrameDataRecorder::NEW;
+        let data = measure(&mut recorder, JAB, &[], 10).unwrap();
+        assert_eq!((data.startup, data.active, data.recovery, data.total), (0, 0, 10, 10));
+    }
+
+    #[test]
+    fn cancelled_moves_start_over() {
+        let mut recorder = FrameDataRecorder::NEW;
+        recorder.observe(1, JAB, false, false);
+        recorder.observe(1, JAB, true, false);
+        let data = measure(&mut recorder, TILT, &[5], 12).unwrap();
+        assert_eq!((data.motion, data.startup, data.total), (TILT, 5, 12));
+    }
+
+    #[test]
+    fn table_replaces_and_exports() {
+        let mut table = FrameDataTable::NEW;
+        let jab = FrameData { fighter_kind: 1, motion: JAB, startup: 3, active: 2, recovery: 10, total: 14 };
+        table.record(FrameData { fighter_kind: 2, ..jab });
+        table.record(FrameData { startup: 9, ..jab });
+        table.record(jab);
+        assert_eq!(table.entries.len(), 2);
+        assert_eq!(table.get(1, JAB), Some(&jab));
+        assert_eq!(table.get(1, TILT), None);
+        assert_eq!(table.export(),
+            "fighter_kind,motion,startup,active,recovery,total\n\
+            1,0x0000000011,3,2,10,14\n\
+            2,0x0000000011,3,2,10,14\n"
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use crate::vars::PLAYER_MAX;
+
+/// Returns the position of a single-bit attack mask (ATTACK_AIR_N_MASK and so on) in a gatling table.
+fn mask_index(mask: i32, len: usize) -> Option<usize> {
+    if mask.count_ones() != 1 {
+        return None;
+    }
+    let index = mask.trailing_zeros() as usize;
+    if index < len {
+        Some(index)
+    }
+    else {
+        None
+    }
+}
+
+/// Returns the mask of attacks that can follow `last` in a string.
+/// Attacks that were already used can't be used again, unless the table lets an attack chain into itself.
+fn allowed_mask(follow_ups: &[i32], last: i32, used: i32, length: i32, max_length: i32) -> i32 {
+    let all = (1 << follow_ups.len()) - 1;
+    if max_length > 0 && length >= max_length {
+        return 0;
+    }
+    match mask_index(last, follow_ups.len()) {
+        Some(index) => {
+            let follow_up = follow_ups[index] & all;
+            follow_up & (!used | last)
+        },
+        None => all & !used
+    }
+}
+
+/// Finds which attack a status or attack kind belongs to, and returns that attack's mask.
+/// `kinds` lists the values for each attack in the same order as a gatling table's follow_ups.
+/// An attack can have more than one, like a smash attack's start, hold and attack statuses.
+/// Returns 0 if the kind isn't listed.
+pub fn mask_for_kind(kind: i32, kinds: &[&[i32]]) -> i32 {
+    kinds.iter()
+        .position(|attack_kinds| attack_kinds.contains(&kind))
+        .map_or(0, |index| 1 << index)
+}
+
+/// Where a fighter is in a string of attacks. Stored in the fighter's work ints between frames.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct GatlingString {
+    /// The mask of the attack currently being done, or 0 if you're not in a string yet.
+    pub last: i32,
+    /// The mask of every attack used so far in the string.
+    pub used: i32,
+    /// How many attacks have been used so far in the string.
+    pub length: i32
+}
+
+impl GatlingString {
+    pub const NEW : GatlingString = GatlingString {
+        last: 0,
+        used: 0,
+        length: 0
+    };
+
+    /// Records an attack as the latest one in the string. Recording the current attack again
+    /// (such as every frame it's active) doesn't make the string any longer.
+    pub fn record(&mut self, mask: i32) {
+        if self.used & mask == 0 || self.last != mask {
+            self.length += 1;
+        }
+        self.used |= mask;
+        self.last = mask;
+    }
+}
+
+/// Describes which aerials may follow which in an aerial string.
+/// Masks use the ATTACK_AIR_XX_MASK constants, and follow_ups is ordered N, F, B, HI, LW.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AerialGatling {
+    /// For each aerial, the mask of aerials that can be cancelled into from it.
+    /// Listing an aerial as its own follow-up allows it to chain into itself.
+    pub follow_ups: [i32; 5],
+    /// The most aerials that can be used in one string, or 0 for no limit.
+    pub max_length: i32,
+    /// If true, jump-cancelling starts a new string.
+    pub jump_resets: bool
+}
+
+impl AerialGatling {
+    /// Checks if `next` can be cancelled into.
+    /// # Arguments
+    ///
+    /// * `last` - The mask of the aerial currently being cancelled, or 0 if you're not in a string yet.
+    /// * `used` - The mask of every aerial used so far in the string.
+    /// * `length` - How many aerials have been used so far in the string.
+    /// * `next` - The mask of the aerial being checked.
+    pub fn is_allowed(&self, last: i32, used: i32, length: i32, next: i32) -> bool {
+        mask_index(next, self.follow_ups.len()).is_some()
+        && allowed_mask(&self.follow_ups, last, used, length, self.max_length) & next != 0
+    }
+
+    /// Returns the string to continue with after a jump-cancel.
+    pub fn string_after_jump(&self, string: GatlingString) -> GatlingString {
+        if self.jump_resets { GatlingString::NEW } else { string }
+    }
+}
+
+/// Describes which grounded normals may follow which in a string, like the gatling tables in anime fighters.
+/// Masks use the ATTACK_XX_MASK constants, and follow_ups is ordered N, S3, HI3, LW3, S4, HI4, LW4, DASH.
+/// Smash attacks are treated as one normal from the start of the charge to the end of the attack.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GroundGatling {
+    /// For each normal, the mask of normals that can be cancelled into from it.
+    /// Listing a normal as its own follow-up allows it to chain into itself, which is how rekkas are set up.
+    pub follow_ups: [i32; 8],
+    /// The most normals that can be used in one string, or 0 for no limit.
+    pub max_length: i32
+}
+
+impl GroundGatling {
+    /// Returns the mask of every normal that can be cancelled into.
+    /// # Arguments
+    ///
+    /// * `last` - The mask of the normal currently being cancelled, or 0 if you're not in a string yet.
+    /// * `used` - The mask of every normal used so far in the string.
+    /// * `length` - How many normals have been used so far in the string.
+    pub fn allowed(&self, last: i32, used: i32, length: i32) -> i32 {
+        allowed_mask(&self.follow_ups, last, used, length, self.max_length)
+    }
+
+    /// Checks if `next` can be cancelled into.
+    pub fn is_allowed(&self, last: i32, used: i32, length: i32, next: i32) -> bool {
+        mask_index(next, self.follow_ups.len()).is_some()
+        && self.allowed(last, used, length) & next != 0
+    }
+}
+
+/// Returns the mask of every grounded normal that can't be cancelled into right now.
+/// Without a gatling table, only the normals already used in the string are locked out.
+pub fn disabled_ground_normals(gatling: Option<GroundGatling>, string: GatlingString) -> i32 {
+    match gatling {
+        Some(gatling) => !gatling.allowed(string.last, string.used, string.length),
+        None => string.used
+    }
+}
+
+/// The aerial gatling table for each player.
+pub static mut AERIAL_GATLINGS : [Option<AerialGatling>; PLAYER_MAX] = [None; PLAYER_MAX];
+
+/// The ground gatling table for each player.
+pub static mut GROUND_GATLINGS : [Option<GroundGatling>; PLAYER_MAX] = [None; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N : i32 = 0b00001;
+    const F : i32 = 0b00010;
+    const B : i32 = 0b00100;
+    const HI : i32 = 0b01000;
+    const LW : i32 = 0b10000;
+
+    /// N -> F -> B -> HI -> LW, with up air able to chain into itself.
+    const AERIALS : AerialGatling = AerialGatling {
+        follow_ups: [F, B, HI, HI | LW, 0],
+        max_length: 0,
+        jump_resets: true
+    };
+
+    /// Plays a string of aerials, returning false as soon as one isn't allowed.
+    fn play(gatling: &AerialGatling, aerials: &[i32]) -> bool {
+        let mut string = GatlingString::NEW;
+        for &aerial in aerials {
+            if !gatling.is_allowed(string.last, string.used, string.length, aerial) {
+                return false;
+            }
+            string.record(aerial);
+        }
+        true
+    }
+
+    #[test]
+    fn aerial_strings_follow_the_table() {
+        assert!(play(&AERIALS, &[N, F, B, HI, LW]));
+        assert!(play(&AERIALS, &[B, HI]));
+        assert!(!play(&AERIALS, &[N, B]));
+        assert!(!play(&AERIALS, &[F, N]));
+        assert!(!play(&AERIALS, &[N, 0b100000]));
+    }
+
+    #[test]
+    fn aerials_only_repeat_when_chained_into_themselves() {
+        assert!(play(&AERIALS, &[HI, HI, HI, LW]));
+        let repeating = AerialGatling { follow_ups: [N | F, N, 0, 0, 0], ..AERIALS };
+        assert!(play(&repeating, &[N, N, F]));
+        assert!(!play(&repeating, &[N, F, N]));
+    }
+
+    #[test]
+    fn strings_have_a_max_length() {
+        let limited = AerialGatling { max_length: 2, ..AERIALS };
+        assert!(play(&limited, &[N, F]));
+        assert!(!play(&limited, &[N, F, B]));
+    }
+
+    #[test]
+    fn recording_the_same_aerial_doesnt_lengthen_the_string() {
+        let mut string = GatlingString::NEW;
+        string.record(N);
+        string.record(N);
+        string.record(F);
+        assert_eq!(string, GatlingString { last: F, used: N | F, length: 2 });
+    }
+
+    #[test]
+    fn jumps_reset_the_string() {
+        let string = GatlingString { last: F, used: N | F, length: 2 };
+        assert_eq!(AERIALS.string_after_jump(string), GatlingString::NEW);
+        let keeps = AerialGatling { jump_resets: false, ..AERIALS };
+        assert_eq!(keeps.string_after_jump(string), string);
+        assert!(AERIALS.is_allowed(0, 0, 0, F));
+    }
+
+    const JAB : i32 = 1 << 0;
+    const TILT_S : i32 = 1 << 1;
+    const TILT_HI : i32 = 1 << 2;
+    const SMASH_S : i32 = 1 << 4;
+    const SMASH_HI : i32 = 1 << 5;
+    const SMASH_LW : i32 = 1 << 6;
+    const DASH : i32 = 1 << 7;
+
+    /// Status kinds laid out the way the grounded normal statuses are passed to mask_for_kind:
+    /// jab and rapid jab, the three tilts, the three smash attacks' start, hold and attack statuses, then dash attack.
+    const GROUND_KINDS : [&[i32]; 8] = [
+        &[0, 1], &[2], &[3], &[4], &[5, 6, 7], &[8, 9, 10], &[11, 12, 13], &[14]
+    ];
+
+    #[test]
+    fn ground_normal_kinds() {
+        assert_eq!(mask_for_kind(1, &GROUND_KINDS), JAB);
+        for kind in 5..=7 {
+            assert_eq!(mask_for_kind(kind, &GROUND_KINDS), SMASH_S);
+        }
+        for kind in 8..=10 {
+            assert_eq!(mask_for_kind(kind, &GROUND_KINDS), SMASH_HI);
+        }
+        for kind in 11..=13 {
+            assert_eq!(mask_for_kind(kind, &GROUND_KINDS), SMASH_LW);
+        }
+        assert_eq!(mask_for_kind(14, &GROUND_KINDS), DASH);
+        assert_eq!(mask_for_kind(15, &GROUND_KINDS), 0);
+    }
+
+    #[test]
+    fn holding_a_smash_attack_stays_one_normal() {
+        let mut string = GatlingString::NEW;
+        for kind in 5..=7 {
+            string.record(mask_for_kind(kind, &GROUND_KINDS));
+        }
+        assert_eq!(string, GatlingString { last: SMASH_S, used: SMASH_S, length: 1 });
+    }
+
+    #[test]
+    fn used_normals_are_locked_out_without_a_table() {
+        let mut string = GatlingString::NEW;
+        string.record(JAB);
+        string.record(TILT_S);
+        assert_eq!(disabled_ground_normals(None, string), JAB | TILT_S);
+        assert_eq!(disabled_ground_normals(None, GatlingString::NEW), 0);
+    }
+
+    #[test]
+    fn ground_tables_lock_out_everything_but_follow_ups() {
+        // Jab -> forward tilt -> up tilt -> forward smash, with dash attack able to start a string.
+        let gatling = GroundGatling {
+            follow_ups: [TILT_S, TILT_HI, SMASH_S, 0, 0, 0, 0, JAB],
+            max_length: 0
+        };
+        let mut string = GatlingString::NEW;
+        string.record(DASH);
+        string.record(JAB);
+        assert_eq!(disabled_ground_normals(Some(gatling), string) & 0xff, !TILT_S & 0xff);
+        string.record(TILT_S);
+        assert!(gatling.is_allowed(string.last, string.used, string.length, TILT_HI));
+        assert!(!gatling.is_allowed(string.last, string.used, string.length, JAB));
+        assert!(!gatling.is_allowed(string.last, string.used, string.length, TILT_S));
+    }
+
+    #[test]
+    fn aerial_kinds() {
+        let kinds : [&[i32]; 5] = [&[10], &[11], &[12], &[13], &[14]];
+        assert_eq!(mask_for_kind(10, &kinds), N);
+        assert_eq!(mask_for_kind(13, &kinds), HI);
+        assert_eq!(mask_for_kind(14, &kinds), LW);
+        assert_eq!(mask_for_kind(0, &kinds), 0);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::vars::PLAYER_MAX;
+
+/// Per-fighter settings for the guard subsystem. Set through FGCModule::set_guard_config.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GuardConfig {
+    /// Allows attacks to cancel the start of the shield drop animation.
+    pub guard_off_cancel: bool,
+    /// How many frames into the shield drop you can still attack.
+    pub guard_off_cancel_frame: i32,
+    /// Allows spending meter to cancel shieldstun into alpha_counter_status.
+    pub alpha_counter: bool,
+    /// The status kind of the counter move.
+    pub alpha_counter_status: i32,
+    /// The cat1 flags that perform the counter (*FIGHTER_PAD_CMD_CAT1_FLAG_XXXXXX).
+    pub alpha_counter_cat1: i32,
+    /// How much meter the counter costs.
+    pub alpha_counter_cost: f32,
+    /// The float work ID the fighter's meter is stored in, as passed to FGCModule::update_meter.
+    pub meter_const: i32,
+    pub meter_max: f32
+}
+
+impl GuardConfig {
+    pub const DISABLED : GuardConfig = GuardConfig {
+        guard_off_cancel: false,
+        guard_off_cancel_frame: 0,
+        alpha_counter: false,
+        alpha_counter_status: 0,
+        alpha_counter_cat1: 0,
+        alpha_counter_cost: 0.0,
+        meter_const: 0,
+        meter_max: 0.0
+    };
+}
+
+impl Default for GuardConfig {
+    fn default() -> Self {
+        GuardConfig::DISABLED
+    }
+}
+
+/// Moves the guard-off attack cancel window forward by a frame.
+/// Returns how many frames will be left after this one, or None if the window has closed.
+/// The window doesn't shrink during hitlag, so blocking an attack on the way out of shield doesn't eat into it.
+pub fn guard_off_window_step(frames_left: i32, in_hitlag: bool) -> Option<i32> {
+    if frames_left <= 0 {
+        return None;
+    }
+    if in_hitlag {
+        return Some(frames_left);
+    }
+    Some(frames_left - 1)
+}
+
+/// Guard settings for each player.
+pub static mut GUARD_CONFIGS : [GuardConfig; PLAYER_MAX] = [GuardConfig::DISABLED; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs the window until it closes, returning how many frames it was open for.
+    fn open_frames(mut frames_left: i32, hitlag: &[bool]) -> usize {
+        let mut frames = 0;
+        while let Some(left) = guard_off_window_step(frames_left, hitlag.get(frames).copied().unwrap_or(false)) {
+            frames_left = left;
+            frames += 1;
+        }
+        frames
+    }
+
+    #[test]
+    fn window_lasts_its_length() {
+        assert_eq!(open_frames(3, &[]), 3);
+        assert_eq!(open_frames(0, &[]), 0);
+        assert_eq!(guard_off_window_step(-2, false), None);
+    }
+
+    #[test]
+    fn hitlag_doesnt_shrink_the_window() {
+        assert_eq!(guard_off_window_step(2, true), Some(2));
+        assert_eq!(open_frames(3, &[false, true, true, true, false]), 6);
+    }
+}
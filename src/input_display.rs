@@ -0,0 +1,268 @@
+use crate::vars::PLAYER_MAX;
+
+/// A direction and set of buttons, along with how many frames in a row they were held.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InputEntry {
+    /// The direction, in numpad notation.
+    pub direction: i32,
+    /// The buttons held, as a bitmask.
+    pub buttons: i32,
+    pub frames: u32
+}
+
+impl InputEntry {
+    /// Turns the entry into text like "6 A+B 12", using `names` to name each button bit.
+    /// Buttons without a name are left out.
+    pub fn label(&self, names: &[(i32, &str)]) -> String {
+        let buttons = names.iter()
+            .filter(|(mask, _)| self.buttons & mask != 0)
+            .map(|(_, name)| *name)
+            .collect::<Vec<&str>>();
+        if buttons.is_empty() {
+            format!("{} {}", self.direction, self.frames)
+        }
+        else {
+            format!("{} {} {}", self.direction, buttons.join("+"), self.frames)
+        }
+    }
+}
+
+/// A running log of inputs, like the input display in training mode of most fighting games.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InputLog {
+    entries: Vec<InputEntry>,
+    /// The most entries kept. Older entries are dropped first.
+    pub max_entries: usize
+}
+
+impl InputLog {
+    pub const NEW : InputLog = InputLog {
+        entries: Vec::new(),
+        max_entries: 20
+    };
+
+    /// Adds a frame of input. Should be called once per frame.
+    /// Returns true if a new entry was started, rather than the latest one being held for longer.
+    pub fn push(&mut self, direction: i32, buttons: i32) -> bool {
+        if let Some(last) = self.entries.last_mut() {
+            if last.direction == direction && last.buttons == buttons {
+                last.frames = last.frames.saturating_add(1);
+                return false;
+            }
+        }
+        self.entries.push(InputEntry { direction, buttons, frames: 1 });
+        if self.entries.len() > self.max_entries {
+            let excess = self.entries.len() - self.max_entries;
+            self.entries.drain(..excess);
+        }
+        true
+    }
+
+    /// Returns the entries, oldest first.
+    pub fn entries(&self) -> &[InputEntry] {
+        &self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl Default for InputLog {
+    fn default() -> Self {
+        InputLog::NEW
+    }
+}
+
+/// Turns a list of per-frame (direction, buttons) pairs into entries, oldest first.
+pub fn aggregate_inputs(frames: &[(i32, i32)]) -> Vec<InputEntry> {
+    let mut log = InputLog {
+        entries: Vec::new(),
+        max_entries: usize::MAX
+    };
+    for &(direction, buttons) in frames {
+        log.push(direction, buttons);
+    }
+    log.entries
+}
+
+/// Turns a direction in numpad notation into the angle (in degrees, counter-clockwise from 6) an arrow pointing that way is rotated by.
+/// Returns None for neutral.
+pub fn direction_angle(direction: i32) -> Option<f32> {
+    match direction {
+        6 => Some(0.0),
+        9 => Some(45.0),
+        8 => Some(90.0),
+        7 => Some(135.0),
+        4 => Some(180.0),
+        1 => Some(225.0),
+        2 => Some(270.0),
+        3 => Some(315.0),
+        _ => None
+    }
+}
+
+/// How the input display is drawn with effects. Effects are hash40s, and any effect left at 0 isn't drawn.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct InputDisplayConfig {
+    /// An arrow pointing right, rotated to show the direction.
+    pub direction_effect: u64,
+    /// Shown instead of the arrow when the stick is neutral.
+    pub neutral_effect: u64,
+    /// The button masks and the effect each one shows when held.
+    pub button_effects: [(i32, u64); 5],
+    /// How many of the newest entries are shown.
+    pub rows: usize,
+    /// Where the newest entry is drawn, relative to the fighter.
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// How far up each older entry is drawn.
+    pub row_spacing: f32,
+    /// How far apart the direction and each button are drawn.
+    pub column_spacing: f32,
+    pub scale: f32
+}
+
+impl InputDisplayConfig {
+    pub const DISABLED : InputDisplayConfig = InputDisplayConfig {
+        direction_effect: 0,
+        neutral_effect: 0,
+        button_effects: [(0, 0); 5],
+        rows: 8,
+        offset_x: -12.0,
+        offset_y: 25.0,
+        row_spacing: 2.5,
+        column_spacing: 2.5,
+        scale: 0.3
+    };
+
+    /// Every effect this config can draw, so they can all be removed before drawing again.
+    pub fn effects(&self) -> Vec<u64> {
+        let mut effects = vec![self.direction_effect, self.neutral_effect];
+        effects.extend(self.button_effects.iter().map(|&(_, effect)| effect));
+        effects.retain(|&effect| effect != 0);
+        effects.dedup();
+        effects
+    }
+}
+
+impl Default for InputDisplayConfig {
+    fn default() -> Self {
+        InputDisplayConfig::DISABLED
+    }
+}
+
+/// One effect to draw for the input display.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct InputSprite {
+    pub effect: u64,
+    pub x: f32,
+    pub y: f32,
+    /// The rotation around the z axis, in degrees.
+    pub rotation: f32
+}
+
+/// Lays out the newest entries as effects, with the newest entry at the bottom.
+pub fn layout_input_display(entries: &[InputEntry], config: &InputDisplayConfig) -> Vec<InputSprite> {
+    let mut sprites = Vec::new();
+    for (row, entry) in entries.iter().rev().take(config.rows).enumerate() {
+        let y = config.offset_y + (row as f32 * config.row_spacing);
+        let (effect, rotation) = match direction_angle(entry.direction) {
+            Some(angle) => (config.direction_effect, angle),
+            None => (config.neutral_effect, 0.0)
+        };
+        if effect != 0 {
+            sprites.push(InputSprite { effect, x: config.offset_x, y, rotation });
+        }
+        let held = config.button_effects.iter().filter(|&&(mask, effect)| effect != 0 && entry.buttons & mask != 0);
+        for (column, &(_, effect)) in held.enumerate() {
+            let x = config.offset_x + ((column + 1) as f32 * config.column_spacing);
+            sprites.push(InputSprite { effect, x, y, rotation: 0.0 });
+        }
+    }
+    sprites
+}
+
+/// How the input display is drawn.
+pub static mut INPUT_DISPLAY_CONFIG : InputDisplayConfig = InputDisplayConfig::DISABLED;
+
+/// The input log for each player.
+pub static mut INPUT_LOGS : [InputLog; PLAYER_MAX] = [InputLog::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A : i32 = 0b01;
+    const B : i32 = 0b10;
+
+    fn entry(direction: i32, buttons: i32, frames: u32) -> InputEntry {
+        InputEntry { direction, buttons, frames }
+    }
+
+    #[test]
+    fn aggregates_held_inputs() {
+        let frames = [(5, 0), (5, 0), (2, 0), (3, 0), (6, A), (6, A), (6, A), (5, 0)];
+        assert_eq!(aggregate_inputs(&frames), [
+            entry(5, 0, 2),
+            entry(2, 0, 1),
+            entry(3, 0, 1),
+            entry(6, A, 3),
+            entry(5, 0, 1)
+        ]);
+    }
+
+    #[test]
+    fn button_changes_start_new_entries() {
+        let frames = [(6, A), (6, A | B), (6, B)];
+        assert_eq!(aggregate_inputs(&frames), [entry(6, A, 1), entry(6, A | B, 1), entry(6, B, 1)]);
+        assert!(aggregate_inputs(&[]).is_empty());
+    }
+
+    #[test]
+    fn log_drops_oldest_entries() {
+        let mut log = InputLog { max_entries: 2, ..InputLog::NEW };
+        assert!(log.push(5, 0));
+        assert!(!log.push(5, 0));
+        assert!(log.push(6, 0));
+        assert!(log.push(4, 0));
+        assert_eq!(log.entries(), [entry(6, 0, 1), entry(4, 0, 1)]);
+    }
+
+    #[test]
+    fn labels() {
+        let names = [(A, "A"), (B, "B")];
+        assert_eq!(entry(6, A | B, 12).label(&names), "6 A+B 12");
+        assert_eq!(entry(2, 0b100, 3).label(&names), "2 3");
+    }
+
+    #[test]
+    fn direction_angles() {
+        assert_eq!(direction_angle(5), None);
+        assert_eq!(direction_angle(6), Some(0.0));
+        assert_eq!(direction_angle(8), Some(90.0));
+        assert_eq!(direction_angle(1), Some(225.0));
+    }
+
+    #[test]
+    fn layout() {
+        let config = InputDisplayConfig {
+            direction_effect: 100,
+            neutral_effect: 0,
+            button_effects: [(A, 200), (B, 300), (0, 0), (0, 0), (0, 0)],
+            rows: 2,
+            offset_x: 0.0,
+            offset_y: 10.0,
+            row_spacing: 1.0,
+            column_spacing: 2.0,
+            scale: 1.0
+        };
+        let entries = [entry(4, 0, 1), entry(5, A, 1), entry(9, B, 1)];
+        assert_eq!(layout_input_display(&entries, &config), [
+            InputSprite { effect: 100, x: 0.0, y: 10.0, rotation: 45.0 },
+            InputSprite { effect: 300, x: 2.0, y: 10.0, rotation: 0.0 },
+            InputSprite { effect: 200, x: 2.0, y: 11.0, rotation: 0.0 }
+        ]);
+        assert_eq!(config.effects(), [100, 200, 300]);
+    }
+}
@@ -2,3 +2,25 @@ pub mod vars;
 pub mod wua_bind;
 pub mod table_const;
 pub mod cancels;
+pub mod cancel_routes;
+pub mod stick;
+pub mod buffer;
+pub mod guard;
+pub mod gatling;
+pub mod target_combo;
+pub mod super_jump;
+pub mod air_dash;
+pub mod wall;
+pub mod appeal;
+pub mod cinematic;
+pub mod super_freeze;
+pub mod cpu;
+pub mod training;
+pub mod input_display;
+pub mod frame_data;
+pub mod counter_hit;
+pub mod stun;
+pub mod throw_tech;
+pub mod pushback;
+pub mod round;
+pub mod round_timer;
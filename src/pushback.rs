@@ -0,0 +1,130 @@
+use crate::vars::PLAYER_MAX;
+
+/// Settings for the pushback a fighter's attacks cause in Fighting Game Mode.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PushbackConfig {
+    pub enabled: bool,
+    /// How far the victim gets pushed on hit.
+    pub hit: f32,
+    /// How far the victim gets pushed on block.
+    pub block: f32,
+    /// How many frames the push is spread over.
+    pub frames: i32
+}
+
+impl PushbackConfig {
+    pub const DISABLED : PushbackConfig = PushbackConfig {
+        enabled: false,
+        hit: 6.0,
+        block: 8.0,
+        frames: 6
+    };
+}
+
+impl Default for PushbackConfig {
+    fn default() -> Self {
+        PushbackConfig::DISABLED
+    }
+}
+
+/// Decides who gets pushed. The victim normally takes the pushback,
+/// but if they're in the corner (against a wall or at a ledge), the attacker gets pushed back instead.
+/// Returns (victim distance, attacker distance).
+pub fn split_pushback(amount: f32, cornered: bool) -> (f32, f32) {
+    if cornered { (0.0, amount) } else { (amount, 0.0) }
+}
+
+/// Moves a fighter by a distance spread out over a number of frames.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PushbackState {
+    remaining: f32,
+    frames_left: i32
+}
+
+impl PushbackState {
+    pub const NEW : PushbackState = PushbackState {
+        remaining: 0.0,
+        frames_left: 0
+    };
+
+    /// Starts a new push, replacing whatever was left of the last one.
+    pub fn start(&mut self, distance: f32, frames: i32) {
+        self.remaining = distance;
+        self.frames_left = frames.max(1);
+    }
+
+    /// Returns how far the next step would move, without taking it.
+    pub fn next_step(&self) -> f32 {
+        if self.frames_left <= 0 {
+            return 0.0;
+        }
+        self.remaining / self.frames_left as f32
+    }
+
+    /// Returns how far to move this frame. Should be called once per frame, skipping frames spent in hitlag.
+    /// # Arguments
+    ///
+    /// * `blocked` - If the fighter is in the corner in the direction of the next step. This ends the push,
+    ///   so fighters never get pushed through walls or off ledges.
+    pub fn step(&mut self, blocked: bool) -> f32 {
+        if blocked {
+            self.stop();
+            return 0.0;
+        }
+        let distance = self.next_step();
+        if distance != 0.0 {
+            self.remaining -= distance;
+            self.frames_left -= 1;
+        }
+        distance
+    }
+
+    /// Ends the push.
+    pub fn stop(&mut self) {
+        *self = PushbackState::NEW;
+    }
+}
+
+impl Default for PushbackState {
+    fn default() -> Self {
+        PushbackState::NEW
+    }
+}
+
+/// The pushback settings for each player's attacks.
+pub static mut PUSHBACK_CONFIGS : [PushbackConfig; PLAYER_MAX] = [PushbackConfig::DISABLED; PLAYER_MAX];
+
+/// The push being applied to each player.
+pub static mut PUSHBACK_STATES : [PushbackState; PLAYER_MAX] = [PushbackState::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cornered_victims_push_the_attacker() {
+        assert_eq!(split_pushback(6.0, false), (6.0, 0.0));
+        assert_eq!(split_pushback(6.0, true), (0.0, 6.0));
+    }
+
+    #[test]
+    fn push_is_spread_over_frames() {
+        let mut push = PushbackState::NEW;
+        push.start(-6.0, 3);
+        assert_eq!(push.next_step(), -2.0);
+        let steps: Vec<f32> = (0..4).map(|_| push.step(false)).collect();
+        assert_eq!(steps, [-2.0, -2.0, -2.0, 0.0]);
+        push.start(1.0, 0);
+        assert_eq!(push.step(false), 1.0);
+    }
+
+    #[test]
+    fn corners_stop_the_push() {
+        let mut push = PushbackState::NEW;
+        push.start(6.0, 3);
+        assert_eq!(push.step(false), 2.0);
+        assert_eq!(push.step(true), 0.0);
+        assert_eq!(push.next_step(), 0.0);
+        assert_eq!(push.step(false), 0.0);
+    }
+}
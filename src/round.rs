@@ -0,0 +1,383 @@
+use crate::vars::PLAYER_MAX;
+
+/// Where a match currently is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatchPhase {
+    /// No match is running.
+    Idle,
+    /// The pre-round intro. Nobody can be KO'd yet.
+    Intro,
+    Fight,
+    /// The slow motion after the round has been decided.
+    RoundKo,
+    /// Someone has won enough rounds. Stays here until the match is reset.
+    MatchEnd
+}
+
+/// Something that happened during a match.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatchEvent {
+    /// A new round's intro started. Rounds are counted from 1.
+    RoundStart { round: u32 },
+    /// The intro finished and fighting can begin.
+    Fight { round: u32 },
+    PlayerKo { player: usize },
+    TimeOver,
+    /// The round was decided. A winner of None is a draw.
+    RoundEnd { round: u32, winner: Option<usize> },
+    /// The match was decided. A winner of None is a draw.
+    MatchEnd { winner: Option<usize> }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MatchConfig {
+    /// How many rounds someone needs to win the match.
+    pub rounds_to_win: u32,
+    /// How many frames the pre-round intro lasts.
+    pub intro_frames: i32,
+    /// How many frames of slow motion there are after a round is decided.
+    pub ko_frames: i32,
+    /// How many seconds each round lasts, or 0 for no time limit.
+    pub time_limit: i32
+}
+
+impl MatchConfig {
+    pub const DEFAULT : MatchConfig = MatchConfig {
+        rounds_to_win: 2,
+        intro_frames: 90,
+        ko_frames: 120,
+        time_limit: 99
+    };
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        MatchConfig::DEFAULT
+    }
+}
+
+/// Runs a match from round to round, keeping score and sending out events.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MatchState {
+    pub config: MatchConfig,
+    phase: MatchPhase,
+    frame: i32,
+    round: u32,
+    active: [bool; PLAYER_MAX],
+    wins: [u32; PLAYER_MAX],
+    ko: [bool; PLAYER_MAX],
+    pending_ko: [bool; PLAYER_MAX],
+    round_winner: Option<usize>,
+    round_winners: [bool; PLAYER_MAX],
+    round_started: [u32; PLAYER_MAX],
+    events: Vec<MatchEvent>
+}
+
+impl MatchState {
+    pub const NEW : MatchState = MatchState {
+        config: MatchConfig::DEFAULT,
+        phase: MatchPhase::Idle,
+        frame: 0,
+        round: 0,
+        active: [false; PLAYER_MAX],
+        wins: [0; PLAYER_MAX],
+        ko: [false; PLAYER_MAX],
+        pending_ko: [false; PLAYER_MAX],
+        round_winner: None,
+        round_winners: [false; PLAYER_MAX],
+        round_started: [0; PLAYER_MAX],
+        events: Vec::new()
+    };
+
+    pub fn phase(&self) -> MatchPhase {
+        self.phase
+    }
+
+    /// The current round, counted from 1, or 0 if no match is running.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn wins(&self, player: usize) -> u32 {
+        self.wins.get(player).copied().unwrap_or(0)
+    }
+
+    pub fn is_fighting(&self) -> bool {
+        self.phase == MatchPhase::Fight
+    }
+
+    /// Starts a new match from round 1.
+    /// # Arguments
+    ///
+    /// * `active` - Which entry IDs are in the match. Entry IDs don't have to be contiguous.
+    pub fn start(&mut self, active: [bool; PLAYER_MAX]) {
+        self.reset();
+        self.active = active;
+        self.start_round();
+    }
+
+    fn players(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..PLAYER_MAX).filter(move |&player| self.active[player])
+    }
+
+    /// Stops the match and clears the score.
+    pub fn reset(&mut self) {
+        let config = self.config;
+        *self = MatchState::NEW;
+        self.config = config;
+    }
+
+    fn start_round(&mut self) {
+        self.round += 1;
+        self.phase = MatchPhase::Intro;
+        self.frame = 0;
+        self.ko = [false; PLAYER_MAX];
+        self.pending_ko = [false; PLAYER_MAX];
+        self.round_winner = None;
+        self.round_winners = [false; PLAYER_MAX];
+        self.events.push(MatchEvent::RoundStart { round: self.round });
+    }
+
+    /// Decides the round. On a draw, everyone in `drawn` gets the round win.
+    fn decide_round(&mut self, winner: Option<usize>, drawn: [bool; PLAYER_MAX]) {
+        self.phase = MatchPhase::RoundKo;
+        self.frame = 0;
+        self.pending_ko = [false; PLAYER_MAX];
+        self.round_winner = winner;
+        self.round_winners = match winner {
+            Some(player) => {
+                let mut winners = [false; PLAYER_MAX];
+                winners[player] = true;
+                winners
+            },
+            None => drawn
+        };
+    }
+
+    /// Returns true once per round for each player, so they can restore their HP.
+    pub fn needs_round_start(&mut self, player: usize) -> bool {
+        if player >= PLAYER_MAX
+        || self.round == 0
+        || self.round_started[player] == self.round {
+            return false;
+        }
+        self.round_started[player] = self.round;
+        true
+    }
+
+    /// Marks a player as KO'd. KOs are resolved together on the next tick,
+    /// so if every remaining player is KO'd on the same frame, the round is a draw.
+    /// Only works during the Fight phase.
+    pub fn ko(&mut self, player: usize) {
+        if self.phase != MatchPhase::Fight
+        || player >= PLAYER_MAX
+        || !self.active[player]
+        || self.ko[player] {
+            return;
+        }
+        self.pending_ko[player] = true;
+    }
+
+    /// Resolves this frame's KOs. Once one player or fewer is left standing, the round is decided,
+    /// and if nobody is left, everyone KO'd on this frame shares the draw.
+    fn resolve_kos(&mut self) {
+        let kos = self.pending_ko;
+        if !kos.iter().any(|&ko| ko) {
+            return;
+        }
+        self.pending_ko = [false; PLAYER_MAX];
+        for player in (0..PLAYER_MAX).filter(|&player| kos[player]) {
+            self.ko[player] = true;
+            self.events.push(MatchEvent::PlayerKo { player });
+        }
+        let alive : Vec<usize> = self.players().filter(|&player| !self.ko[player]).collect();
+        match alive.len() {
+            0 => self.decide_round(None, kos),
+            1 => self.decide_round(Some(alive[0]), [false; PLAYER_MAX]),
+            _ => {}
+        }
+    }
+
+    /// Ends the round because time ran out. A winner of None is a draw, and everyone still standing gets the round win.
+    /// Only works during the Fight phase.
+    pub fn time_over(&mut self, winner: Option<usize>) {
+        if self.phase != MatchPhase::Fight {
+            return;
+        }
+        self.events.push(MatchEvent::TimeOver);
+        let mut standing = [false; PLAYER_MAX];
+        for player in self.players() {
+            standing[player] = !self.ko[player];
+        }
+        self.decide_round(winner, standing);
+    }
+
+    /// Moves the match forward by one frame. Should be called once per frame.
+    pub fn tick(&mut self) {
+        match self.phase {
+            MatchPhase::Intro => {
+                self.frame += 1;
+                if self.frame >= self.config.intro_frames {
+                    self.phase = MatchPhase::Fight;
+                    self.frame = 0;
+                    self.events.push(MatchEvent::Fight { round: self.round });
+                }
+            },
+            MatchPhase::Fight => self.resolve_kos(),
+            MatchPhase::RoundKo => {
+                self.frame += 1;
+                if self.frame >= self.config.ko_frames {
+                    self.end_round();
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Gives out the round win (to everyone who shared a draw), then starts the next round or ends the match.
+    fn end_round(&mut self) {
+        for player in 0..PLAYER_MAX {
+            if self.round_winners[player] {
+                self.wins[player] += 1;
+            }
+        }
+        self.events.push(MatchEvent::RoundEnd { round: self.round, winner: self.round_winner });
+        let match_winners : Vec<usize> = self.players().filter(|&player| self.wins[player] >= self.config.rounds_to_win).collect();
+        if match_winners.is_empty() {
+            self.start_round();
+        }
+        else {
+            let winner = if match_winners.len() == 1 { Some(match_winners[0]) } else { None };
+            self.phase = MatchPhase::MatchEnd;
+            self.frame = 0;
+            self.events.push(MatchEvent::MatchEnd { winner });
+        }
+    }
+
+    /// Returns every event since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<MatchEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+impl Default for MatchState {
+    fn default() -> Self {
+        MatchState::NEW
+    }
+}
+
+/// Called for every match event, once per event.
+pub type MatchEventListener = fn(event: &MatchEvent);
+
+/// How many match event listeners can be added.
+pub const MATCH_EVENT_LISTENER_MAX : usize = 8;
+
+/// The match currently running.
+pub static mut MATCH_STATE : MatchState = MatchState::NEW;
+
+/// Everything listening for match events.
+pub static mut MATCH_EVENT_LISTENERS : [Option<MatchEventListener>; MATCH_EVENT_LISTENER_MAX] = [None; MATCH_EVENT_LISTENER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG : MatchConfig = MatchConfig {
+        rounds_to_win: 2,
+        intro_frames: 3,
+        ko_frames: 2,
+        time_limit: 99
+    };
+
+    /// Starts a match between the given entry IDs and plays through the intro.
+    fn fight(players: &[usize]) -> MatchState {
+        let mut active = [false; PLAYER_MAX];
+        for &player in players {
+            active[player] = true;
+        }
+        let mut state = MatchState { config: CONFIG, ..MatchState::NEW };
+        state.start(active);
+        skip_intro(&mut state);
+        state
+    }
+
+    fn skip_intro(state: &mut MatchState) {
+        for _ in 0..CONFIG.intro_frames {
+            assert_eq!(state.phase(), MatchPhase::Intro);
+            state.tick();
+        }
+        assert!(state.is_fighting());
+    }
+
+    fn skip_ko(state: &mut MatchState) {
+        for _ in 0..CONFIG.ko_frames {
+            assert_eq!(state.phase(), MatchPhase::RoundKo);
+            state.tick();
+        }
+    }
+
+    #[test]
+    fn plays_a_match() {
+        let mut state = fight(&[0, 3]);
+        assert_eq!(state.take_events(), [MatchEvent::RoundStart { round: 1 }, MatchEvent::Fight { round: 1 }]);
+        state.ko(1);
+        state.ko(3);
+        state.ko(3);
+        state.tick();
+        skip_ko(&mut state);
+        assert_eq!(state.take_events(), [
+            MatchEvent::PlayerKo { player: 3 },
+            MatchEvent::RoundEnd { round: 1, winner: Some(0) },
+            MatchEvent::RoundStart { round: 2 }
+        ]);
+        assert!(state.needs_round_start(3));
+        assert!(!state.needs_round_start(3));
+        skip_intro(&mut state);
+        state.ko(3);
+        state.tick();
+        skip_ko(&mut state);
+        assert_eq!(state.phase(), MatchPhase::MatchEnd);
+        assert_eq!((state.wins(0), state.wins(3)), (2, 0));
+        assert_eq!(state.take_events().last(), Some(&MatchEvent::MatchEnd { winner: Some(0) }));
+    }
+
+    #[test]
+    fn time_over_draw_gives_everyone_standing_the_round() {
+        let mut state = fight(&[0, 1, 2]);
+        state.ko(2);
+        state.tick();
+        state.time_over(None);
+        skip_ko(&mut state);
+        assert_eq!((state.wins(0), state.wins(1), state.wins(2)), (1, 1, 0));
+    }
+
+    #[test]
+    fn double_ko_is_a_draw() {
+        let mut state = fight(&[0, 1]);
+        state.ko(0);
+        state.ko(1);
+        state.tick();
+        assert_eq!(state.phase(), MatchPhase::RoundKo);
+        skip_ko(&mut state);
+        assert_eq!((state.wins(0), state.wins(1)), (1, 1));
+        skip_intro(&mut state);
+        state.ko(0);
+        state.ko(1);
+        state.tick();
+        skip_ko(&mut state);
+        assert_eq!(state.take_events().last(), Some(&MatchEvent::MatchEnd { winner: None }));
+    }
+
+    #[test]
+    fn kos_only_count_while_fighting() {
+        let mut active = [false; PLAYER_MAX];
+        active[0] = true;
+        active[1] = true;
+        let mut state = MatchState { config: CONFIG, ..MatchState::NEW };
+        state.start(active);
+        state.ko(1);
+        skip_intro(&mut state);
+        state.tick();
+        assert!(state.is_fighting());
+    }
+}
@@ -0,0 +1,165 @@
+/// How many frames the game runs per second.
+pub const FRAMES_PER_SECOND : f32 = 60.0;
+
+/// Counts down the time left in a round.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RoundTimer {
+    frames_left: f32,
+    round: u32,
+    limited: bool,
+    expired: bool
+}
+
+impl RoundTimer {
+    pub const NEW : RoundTimer = RoundTimer {
+        frames_left: 0.0,
+        round: 0,
+        limited: false,
+        expired: false
+    };
+
+    /// Starts the timer for a round. A time limit of 0 or less means the round has no time limit.
+    pub fn start(&mut self, seconds: i32, round: u32) {
+        self.frames_left = seconds.max(0) as f32 * FRAMES_PER_SECOND;
+        self.round = round;
+        self.limited = seconds > 0;
+        self.expired = false;
+    }
+
+    /// The round the timer was last started for.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// The time left as shown on screen, rounded up to the next second.
+    pub fn seconds_left(&self) -> i32 {
+        (self.frames_left / FRAMES_PER_SECOND).ceil() as i32
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Counts down by `frames`, which should already be scaled by slowdown. Does nothing while paused.
+    /// Returns true on the frame time runs out.
+    pub fn tick(&mut self, frames: f32, paused: bool) -> bool {
+        if paused
+        || !self.limited
+        || self.expired {
+            return false;
+        }
+        self.frames_left -= frames;
+        if self.frames_left <= 0.0 {
+            self.frames_left = 0.0;
+            self.expired = true;
+            return true;
+        }
+        false
+    }
+}
+
+impl Default for RoundTimer {
+    fn default() -> Self {
+        RoundTimer::NEW
+    }
+}
+
+/// Returns the percentage (0.0 to 1.0) of HP a fighter has left.
+/// HP mode counts HP through damage: each round starts every fighter on `start_damage` (see FGCModule::restore_hp),
+/// which is full HP, and a fighter has no HP left once their damage reaches `max_hp`.
+pub fn hp_percentage(damage: f32, start_damage: f32, max_hp: f32) -> f32 {
+    let hp = max_hp - start_damage;
+    if hp <= 0.0 {
+        return 0.0;
+    }
+    ((max_hp - damage) / hp).clamp(0.0, 1.0)
+}
+
+/// Decides who wins on time over: whoever has the highest percentage of their HP left.
+/// Returns None on a draw, when more than one player is tied for the most HP.
+/// # Arguments
+///
+/// * `hp_percentages` - The percentage of HP each player has left, indexed by entry ID. None for entry IDs nobody is using.
+pub fn resolve_time_over(hp_percentages: &[Option<f32>]) -> Option<usize> {
+    let best = hp_percentages.iter().flatten().cloned().fold(f32::MIN, f32::max);
+    let mut leaders = hp_percentages.iter()
+        .enumerate()
+        .filter_map(|(player, hp)| hp.map(|hp| (player, hp)))
+        .filter(|&(_, hp)| (best - hp).abs() < 0.0001)
+        .map(|(player, _)| player);
+    let first = leaders.next();
+    if leaders.next().is_some() {
+        return None;
+    }
+    first
+}
+
+/// The timer for the round currently being played.
+pub static mut ROUND_TIMER : RoundTimer = RoundTimer::NEW;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_down_and_expires_once() {
+        let mut timer = RoundTimer::NEW;
+        timer.start(2, 1);
+        assert_eq!(timer.seconds_left(), 2);
+        assert!(!timer.tick(FRAMES_PER_SECOND * 0.5, false));
+        assert_eq!(timer.seconds_left(), 2);
+        assert!(!timer.tick(FRAMES_PER_SECOND, false));
+        assert_eq!(timer.seconds_left(), 1);
+        assert!(timer.tick(FRAMES_PER_SECOND, false));
+        assert!(timer.is_expired());
+        assert_eq!(timer.seconds_left(), 0);
+        assert!(!timer.tick(1.0, false));
+    }
+
+    #[test]
+    fn pauses_and_unlimited_time() {
+        let mut timer = RoundTimer::NEW;
+        timer.start(1, 1);
+        for _ in 0..120 {
+            assert!(!timer.tick(1.0, true));
+        }
+        assert_eq!(timer.seconds_left(), 1);
+        timer.start(0, 2);
+        assert_eq!(timer.round(), 2);
+        assert!(!timer.tick(1000.0, false));
+        assert!(!timer.is_expired());
+    }
+
+    #[test]
+    fn hp_counts_down_from_the_round_start() {
+        assert_eq!(hp_percentage(50.0, 50.0, 150.0), 1.0);
+        assert_eq!(hp_percentage(100.0, 50.0, 150.0), 0.5);
+        assert_eq!(hp_percentage(150.0, 50.0, 150.0), 0.0);
+        assert_eq!(hp_percentage(200.0, 50.0, 150.0), 0.0);
+        assert_eq!(hp_percentage(0.0, 0.0, 100.0), 1.0);
+        assert_eq!(hp_percentage(10.0, 100.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn time_over_goes_to_whoever_took_the_least_damage_this_round() {
+        let (start_damage, max_hp) = (50.0, 150.0);
+        let resolve = |damages: &[Option<f32>]| {
+            let hp_percentages : Vec<Option<f32>> = damages.iter()
+                .map(|damage| damage.map(|damage| hp_percentage(damage, start_damage, max_hp)))
+                .collect();
+            resolve_time_over(&hp_percentages)
+        };
+        // Nobody has been hit yet, so everyone still has all of their HP.
+        assert_eq!(resolve(&[Some(start_damage), None, Some(start_damage)]), None);
+        assert_eq!(resolve(&[Some(start_damage), None, Some(60.0)]), Some(0));
+        assert_eq!(resolve(&[Some(120.0), Some(90.0), None]), Some(1));
+    }
+
+    #[test]
+    fn most_hp_wins_time_over() {
+        assert_eq!(resolve_time_over(&[Some(0.2), None, Some(0.5)]), Some(2));
+        assert_eq!(resolve_time_over(&[Some(0.5), None, Some(0.5)]), None);
+        assert_eq!(resolve_time_over(&[Some(0.0), None, None]), Some(0));
+        assert_eq!(resolve_time_over(&[None, None]), None);
+    }
+}
@@ -0,0 +1,333 @@
+// Pure direction resolving logic. Nothing in here touches the game, so the
+// FFI side (FGCModule::get_command_stick_direction) only has to read the stick
+// and pass the values along.
+
+use crate::vars::PLAYER_MAX;
+
+/// How many directions the stick can resolve to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DirectionMode {
+    /// All 8 directions, plus neutral.
+    EightWay,
+    /// Only 2, 4, 6 and 8, plus neutral. Angles inside the horizontal band are 4 or 6, angles past the vertical threshold are 2 or 8,
+    /// and anything in between snaps to whichever of the two it's closer to, with ties going to the horizontal direction.
+    FourWay
+}
+
+/// How simultaneous opposing cardinal directions (SOCD) are resolved for digital inputs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SocdMode {
+    /// The most recently pressed direction wins on both axes.
+    LastInputWins,
+    /// Opposing directions cancel out into neutral on both axes.
+    Neutral,
+    /// Up wins over down, left + right is neutral.
+    UpPriority
+}
+
+/// Per-player settings for turning stick values into numpad notation.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StickConfig {
+    /// Stick length below which the stick is considered neutral.
+    pub deadzone: f32,
+    /// Angle (in degrees, from horizontal) within which the stick counts as 4 or 6.
+    pub horizontal_band: f32,
+    /// Angle (in degrees, from horizontal) at or above which the stick counts as 2 or 8.
+    pub vertical_threshold: f32,
+    pub mode: DirectionMode,
+    pub socd: SocdMode
+}
+
+impl StickConfig {
+    /// The values FGCModule::get_command_stick_direction has always used.
+    pub const DEFAULT : StickConfig = StickConfig {
+        deadzone: 0.25,
+        horizontal_band: 15.0,
+        vertical_threshold: 70.0,
+        mode: DirectionMode::EightWay,
+        socd: SocdMode::LastInputWins
+    };
+}
+
+impl Default for StickConfig {
+    fn default() -> Self {
+        StickConfig::DEFAULT
+    }
+}
+
+/// Remembers which digital directions were held last frame, so LastInputWins knows
+/// which of two opposing directions was pressed most recently.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SocdState {
+    held: [bool; 4],
+    last_x: i32,
+    last_y: i32
+}
+
+impl SocdState {
+    pub const NEW : SocdState = SocdState {
+        held: [false; 4],
+        last_x: 0,
+        last_y: 0
+    };
+
+    /// Resolves the held digital directions into a pair of axes, each being -1, 0 or 1.
+    /// Must be called once per frame for LastInputWins to work.
+    pub fn resolve(&mut self, left: bool, right: bool, down: bool, up: bool, mode: SocdMode) -> (i32, i32) {
+        if left && !self.held[0] {
+            self.last_x = -1;
+        }
+        if right && !self.held[1] {
+            self.last_x = 1;
+        }
+        if down && !self.held[2] {
+            self.last_y = -1;
+        }
+        if up && !self.held[3] {
+            self.last_y = 1;
+        }
+        self.held = [left, right, down, up];
+        let x = match (left, right) {
+            (true, true) => if mode == SocdMode::LastInputWins { self.last_x } else { 0 },
+            (true, false) => -1,
+            (false, true) => 1,
+            (false, false) => 0
+        };
+        let y = match (down, up) {
+            (true, true) => match mode {
+                SocdMode::LastInputWins => self.last_y,
+                SocdMode::Neutral => 0,
+                SocdMode::UpPriority => 1
+            },
+            (true, false) => -1,
+            (false, true) => 1,
+            (false, false) => 0
+        };
+        (x, y)
+    }
+}
+
+/// Detects double-taps (pressing a direction, letting go, then pressing it again quickly) from the stick's direction history.
+/// The stick has to go back to neutral between the two taps, so rolling through a diagonal doesn't count.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TapTracker {
+    last_dir: i32,
+    tap_dir: i32,
+    released: bool,
+    frames_since_tap: u32,
+    /// How many frames can pass between the two taps.
+    pub window: u32
+}
+
+impl TapTracker {
+    pub const NEW : TapTracker = TapTracker {
+        last_dir: 5,
+        tap_dir: 5,
+        released: false,
+        frames_since_tap: 0,
+        window: 12
+    };
+
+    /// Records this frame's direction, in numpad notation. Should be called once per frame.
+    /// Returns the direction that was double-tapped this frame, or 5 if nothing was.
+    pub fn update(&mut self, dir: i32) -> i32 {
+        let mut tapped = 5;
+        self.frames_since_tap = self.frames_since_tap.saturating_add(1);
+        if dir == 5 {
+            self.released = self.tap_dir != 5;
+        }
+        else if dir != self.last_dir {
+            if dir == self.tap_dir
+            && self.released
+            && self.frames_since_tap <= self.window {
+                tapped = dir;
+                self.tap_dir = 5;
+            }
+            else {
+                self.tap_dir = dir;
+            }
+            self.released = false;
+            self.frames_since_tap = 0;
+        }
+        self.last_dir = dir;
+        tapped
+    }
+}
+
+impl Default for TapTracker {
+    fn default() -> Self {
+        TapTracker::NEW
+    }
+}
+
+/// Turns a pair of axes (-1, 0 or 1 each) into numpad notation.
+pub fn direction_from_axes(x: i32, y: i32, mode: DirectionMode) -> i32 {
+    let x = x.signum();
+    let mut y = y.signum();
+    if mode == DirectionMode::FourWay && x != 0 {
+        y = 0;
+    }
+    5 + x + (y * 3)
+}
+
+/// Turns analog stick values into numpad notation.
+/// The horizontal value should already be flipped if you want it relative to the way the fighter is facing.
+pub fn direction_from_stick(stick_x: f32, stick_y: f32, config: &StickConfig) -> i32 {
+    let length = (stick_x * stick_x + stick_y * stick_y).sqrt();
+    if length < config.deadzone || length == 0.0 {
+        return 5;
+    }
+    let degrees = stick_y.atan2(stick_x.abs()).to_degrees().abs();
+    let x = if stick_x > 0.0 { 1 } else { -1 };
+    let y = if stick_y > 0.0 { 1 } else { -1 };
+    if config.mode == DirectionMode::FourWay {
+        if degrees <= (config.horizontal_band + config.vertical_threshold) / 2.0 {
+            return direction_from_axes(x, 0, config.mode);
+        }
+        return direction_from_axes(0, y, config.mode);
+    }
+    if degrees <= config.horizontal_band {
+        direction_from_axes(x, 0, config.mode)
+    }
+    else if config.vertical_threshold <= degrees {
+        direction_from_axes(0, y, config.mode)
+    }
+    else {
+        direction_from_axes(x, y, config.mode)
+    }
+}
+
+/// Stick settings for each player.
+pub static mut STICK_CONFIGS : [StickConfig; PLAYER_MAX] = [StickConfig::DEFAULT; PLAYER_MAX];
+
+/// SOCD state for each player.
+pub static mut SOCD_STATES : [SocdState; PLAYER_MAX] = [SocdState::NEW; PLAYER_MAX];
+
+/// Double-tap detection for each player.
+pub static mut TAP_TRACKERS : [TapTracker; PLAYER_MAX] = [TapTracker::NEW; PLAYER_MAX];
+
+/// The direction double-tapped on the current frame by each player.
+pub static mut DOUBLE_TAP_DIRS : [i32; PLAYER_MAX] = [5; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn four_way() -> StickConfig {
+        StickConfig { mode: DirectionMode::FourWay, ..StickConfig::DEFAULT }
+    }
+
+    fn at_angle(degrees: f32) -> (f32, f32) {
+        let radians = degrees.to_radians();
+        (radians.cos(), radians.sin())
+    }
+
+    #[test]
+    fn deadzone_is_neutral() {
+        let config = StickConfig::DEFAULT;
+        assert_eq!(direction_from_stick(0.0, 0.0, &config), 5);
+        assert_eq!(direction_from_stick(0.2, 0.1, &config), 5);
+        assert_eq!(direction_from_stick(0.25, 0.0, &config), 6);
+        assert_eq!(direction_from_stick(-0.3, 0.0, &config), 4);
+    }
+
+    #[test]
+    fn band_edges() {
+        let config = StickConfig::DEFAULT;
+        let (x, y) = at_angle(14.9);
+        assert_eq!(direction_from_stick(x, y, &config), 6);
+        let (x, y) = at_angle(15.1);
+        assert_eq!(direction_from_stick(x, y, &config), 9);
+        let (x, y) = at_angle(69.9);
+        assert_eq!(direction_from_stick(x, y, &config), 9);
+        let (x, y) = at_angle(70.1);
+        assert_eq!(direction_from_stick(x, y, &config), 8);
+        let (x, y) = at_angle(-30.0);
+        assert_eq!(direction_from_stick(-x, y, &config), 1);
+        assert_eq!(direction_from_stick(0.0, -1.0, &config), 2);
+    }
+
+    #[test]
+    fn custom_bands() {
+        let config = StickConfig { horizontal_band: 30.0, vertical_threshold: 60.0, ..StickConfig::DEFAULT };
+        let (x, y) = at_angle(25.0);
+        assert_eq!(direction_from_stick(x, y, &config), 6);
+        let (x, y) = at_angle(65.0);
+        assert_eq!(direction_from_stick(x, y, &config), 8);
+    }
+
+    #[test]
+    fn four_way_uses_the_bands() {
+        let config = four_way();
+        let (x, y) = at_angle(40.0);
+        assert_eq!(direction_from_stick(x, y, &config), 6);
+        let (x, y) = at_angle(45.0);
+        assert_eq!(direction_from_stick(x, y, &config), 8);
+        let config = StickConfig { horizontal_band: 5.0, vertical_threshold: 35.0, ..four_way() };
+        let (x, y) = at_angle(25.0);
+        assert_eq!(direction_from_stick(-x, -y, &config), 2);
+        let (x, y) = at_angle(15.0);
+        assert_eq!(direction_from_stick(-x, -y, &config), 4);
+    }
+
+    #[test]
+    fn four_way_axes_drop_diagonals() {
+        assert_eq!(direction_from_axes(1, 1, DirectionMode::FourWay), 6);
+        assert_eq!(direction_from_axes(0, -1, DirectionMode::FourWay), 2);
+        assert_eq!(direction_from_axes(-1, -1, DirectionMode::EightWay), 1);
+    }
+
+    fn taps(dirs: &[i32]) -> Vec<i32> {
+        let mut tracker = TapTracker::NEW;
+        dirs.iter().map(|&dir| tracker.update(dir)).collect()
+    }
+
+    #[test]
+    fn double_tap_through_neutral() {
+        assert_eq!(taps(&[5, 6, 5, 6]), [5, 5, 5, 6]);
+        assert_eq!(taps(&[4, 4, 5, 5, 4]), [5, 5, 5, 5, 4]);
+    }
+
+    #[test]
+    fn double_tap_needs_neutral() {
+        assert_eq!(taps(&[6, 3, 6]), [5, 5, 5]);
+        assert_eq!(taps(&[6, 5, 3, 6]), [5, 5, 5, 5]);
+    }
+
+    #[test]
+    fn double_tap_window() {
+        let mut dirs = vec![6];
+        dirs.extend([5; 11].iter());
+        dirs.push(6);
+        assert_eq!(*taps(&dirs).last().unwrap(), 6);
+        let mut dirs = vec![6];
+        dirs.extend([5; 12].iter());
+        dirs.push(6);
+        assert_eq!(*taps(&dirs).last().unwrap(), 5);
+    }
+
+    #[test]
+    fn socd_last_input_wins() {
+        let mut state = SocdState::NEW;
+        assert_eq!(state.resolve(true, false, false, false, SocdMode::LastInputWins), (-1, 0));
+        assert_eq!(state.resolve(true, true, false, false, SocdMode::LastInputWins), (1, 0));
+        assert_eq!(state.resolve(false, true, false, true, SocdMode::LastInputWins), (1, 1));
+        assert_eq!(state.resolve(false, true, true, true, SocdMode::LastInputWins), (1, -1));
+    }
+
+    #[test]
+    fn socd_neutral() {
+        let mut state = SocdState::NEW;
+        assert_eq!(state.resolve(true, true, false, false, SocdMode::Neutral), (0, 0));
+        assert_eq!(state.resolve(true, true, true, true, SocdMode::Neutral), (0, 0));
+        assert_eq!(state.resolve(true, false, true, false, SocdMode::Neutral), (-1, -1));
+    }
+
+    #[test]
+    fn socd_up_priority() {
+        let mut state = SocdState::NEW;
+        assert_eq!(state.resolve(false, false, true, false, SocdMode::UpPriority), (0, -1));
+        assert_eq!(state.resolve(false, false, true, true, SocdMode::UpPriority), (0, 1));
+        assert_eq!(state.resolve(true, true, false, false, SocdMode::UpPriority), (0, 0));
+    }
+}
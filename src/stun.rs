@@ -0,0 +1,146 @@
+use crate::vars::PLAYER_MAX;
+
+/// Changes how many frames of hitstun or shieldstun a hit causes.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum StunModifier {
+    /// Leaves the stun as the game calculated it.
+    #[default]
+    Default,
+    /// Always causes this many frames, no matter the knockback.
+    Fixed(i32),
+    /// Adds this many frames. Can be negative.
+    Add(i32),
+    /// Multiplies the stun by this amount.
+    Scale(f32)
+}
+
+impl StunModifier {
+    /// Applies the modifier to the stun the game calculated. Never goes below 0.
+    pub fn apply(&self, stun: f32) -> f32 {
+        let stun = match *self {
+            StunModifier::Default => stun,
+            StunModifier::Fixed(frames) => frames as f32,
+            StunModifier::Add(frames) => stun + frames as f32,
+            StunModifier::Scale(scale) => stun * scale
+        };
+        stun.max(0.0)
+    }
+}
+
+/// How a move changes hitstun and shieldstun.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct StunConfig {
+    pub hitstun: StunModifier,
+    pub shieldstun: StunModifier
+}
+
+impl StunConfig {
+    pub const DEFAULT : StunConfig = StunConfig {
+        hitstun: StunModifier::Default,
+        shieldstun: StunModifier::Default
+    };
+}
+
+/// A fighter's stun settings: one for all of their moves, plus overrides for specific moves.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct StunTable {
+    pub default: StunConfig,
+    /// Overrides for specific motion kinds (as hash40s).
+    pub moves: Vec<(u64, StunConfig)>
+}
+
+impl StunTable {
+    pub const NEW : StunTable = StunTable {
+        default: StunConfig::DEFAULT,
+        moves: Vec::new()
+    };
+
+    /// Sets the stun settings for one move, replacing any it already had.
+    pub fn set_move(&mut self, motion: u64, config: StunConfig) {
+        match self.moves.iter_mut().find(|(move_motion, _)| *move_motion == motion) {
+            Some(entry) => entry.1 = config,
+            None => self.moves.push((motion, config))
+        }
+    }
+
+    /// Returns the stun settings for a move, falling back to the fighter's default.
+    pub fn for_motion(&self, motion: u64) -> StunConfig {
+        self.moves.iter()
+            .find(|(move_motion, _)| *move_motion == motion)
+            .map_or(self.default, |(_, config)| *config)
+    }
+}
+
+/// Spots new hits (or blocks) by watching for the stun going up, so each one only gets modified once.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StunTracker {
+    last: f32
+}
+
+impl StunTracker {
+    pub const NEW : StunTracker = StunTracker {
+        last: 0.0
+    };
+
+    /// Returns true if `stun` is from a new hit. Should be called once per frame.
+    pub fn is_new_stun(&mut self, stun: f32) -> bool {
+        let is_new = stun > self.last;
+        self.last = stun;
+        is_new
+    }
+
+    /// Remembers the stun after it was modified, so the modified value doesn't count as a new hit.
+    pub fn set(&mut self, stun: f32) {
+        self.last = stun;
+    }
+}
+
+impl Default for StunTracker {
+    fn default() -> Self {
+        StunTracker::NEW
+    }
+}
+
+/// The stun settings for each player's moves.
+pub static mut STUN_TABLES : [StunTable; PLAYER_MAX] = [StunTable::NEW; PLAYER_MAX];
+
+/// The stun tracker for each player.
+pub static mut STUN_TRACKERS : [StunTracker; PLAYER_MAX] = [StunTracker::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JAB : u64 = 0x11;
+
+    #[test]
+    fn modifiers() {
+        assert_eq!(StunModifier::default(), StunModifier::Default);
+        assert_eq!(StunModifier::Default.apply(12.0), 12.0);
+        assert_eq!(StunModifier::Fixed(20).apply(12.0), 20.0);
+        assert_eq!(StunModifier::Add(3).apply(12.0), 15.0);
+        assert_eq!(StunModifier::Add(-20).apply(12.0), 0.0);
+        assert_eq!(StunModifier::Scale(0.5).apply(12.0), 6.0);
+    }
+
+    #[test]
+    fn moves_override_the_default() {
+        let mut table = StunTable { default: StunConfig { hitstun: StunModifier::Add(2), ..StunConfig::DEFAULT }, ..StunTable::NEW };
+        let jab = StunConfig { hitstun: StunModifier::Fixed(10), shieldstun: StunModifier::Add(1) };
+        assert_eq!(table.for_motion(JAB), table.default);
+        table.set_move(JAB, StunConfig::DEFAULT);
+        table.set_move(JAB, jab);
+        assert_eq!(table.moves.len(), 1);
+        assert_eq!(table.for_motion(JAB), jab);
+    }
+
+    #[test]
+    fn modified_stun_isnt_a_new_hit() {
+        let mut tracker = StunTracker::NEW;
+        assert!(tracker.is_new_stun(20.0));
+        tracker.set(30.0);
+        assert!(!tracker.is_new_stun(29.0));
+        assert!(!tracker.is_new_stun(28.0));
+        assert!(tracker.is_new_stun(40.0));
+    }
+}
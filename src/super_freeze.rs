@@ -0,0 +1,156 @@
+use crate::vars::PLAYER_MAX;
+
+/// How a fighter's super freeze looks. Set through FGCModule::set_super_freeze_config.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SuperFreezeConfig {
+    /// How many frames everyone is frozen for.
+    pub frames: i32,
+    /// The effect played on the fighter as the freeze starts (as a hash40), or 0 for none.
+    pub flash_effect: u64,
+    /// How far the camera zooms in on the fighter. Skipped when there are more than two players,
+    /// or while the game is playing its own cut-in camera.
+    pub zoom: f32
+}
+
+impl SuperFreezeConfig {
+    pub const DEFAULT : SuperFreezeConfig = SuperFreezeConfig {
+        frames: 30,
+        flash_effect: 0,
+        zoom: 1.5
+    };
+}
+
+impl Default for SuperFreezeConfig {
+    fn default() -> Self {
+        SuperFreezeConfig::DEFAULT
+    }
+}
+
+/// Tracks the super freeze (superflash) that happens when a fighter spends meter on a super move.
+/// Only one super freeze can happen at a time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SuperFreeze {
+    owner: i32,
+    frames: i32,
+    zoomed: bool
+}
+
+impl SuperFreeze {
+    pub const NEW : SuperFreeze = SuperFreeze {
+        owner: -1,
+        frames: 0,
+        zoomed: false
+    };
+
+    /// Starts a super freeze for the player with the given entry ID.
+    /// Returns false if another super freeze is already happening.
+    pub fn start(&mut self, owner: i32, frames: i32) -> bool {
+        if self.is_active() || frames <= 0 {
+            return false;
+        }
+        self.owner = owner;
+        self.frames = frames;
+        self.zoomed = false;
+        true
+    }
+
+    /// Remembers that the camera zoomed in for this super freeze, so it gets zoomed back out when the freeze ends.
+    pub fn set_zoomed(&mut self) {
+        if self.is_active() {
+            self.zoomed = true;
+        }
+    }
+
+    /// Checks if the camera zoomed in for the current (or just ended) super freeze.
+    pub fn is_zoomed(&self) -> bool {
+        self.zoomed
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.frames > 0
+    }
+
+    /// Returns the entry ID of the player who started the super freeze, or -1 if nothing is frozen.
+    pub fn owner(&self) -> i32 {
+        if self.is_active() { self.owner } else { -1 }
+    }
+
+    pub fn frames_left(&self) -> i32 {
+        self.frames.max(0)
+    }
+
+    /// Moves the super freeze forward one frame. Should be called once per frame for the whole game, not once per fighter.
+    /// Returns true on the frame it ends.
+    pub fn tick(&mut self) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+        self.frames -= 1;
+        if self.frames <= 0 {
+            self.owner = -1;
+            return true;
+        }
+        false
+    }
+
+    /// Ends the super freeze early, such as when its owner gets KO'd. Returns true if one was happening.
+    pub fn stop(&mut self) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+        self.frames = 0;
+        self.owner = -1;
+        true
+    }
+}
+
+impl Default for SuperFreeze {
+    fn default() -> Self {
+        SuperFreeze::NEW
+    }
+}
+
+/// The current super freeze. There's only one for the whole game.
+pub static mut SUPER_FREEZE : SuperFreeze = SuperFreeze::NEW;
+
+/// The super freeze settings for each player.
+pub static mut SUPER_FREEZE_CONFIGS : [SuperFreezeConfig; PLAYER_MAX] = [SuperFreezeConfig::DEFAULT; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_one_at_a_time() {
+        let mut freeze = SuperFreeze::NEW;
+        assert!(!freeze.start(0, 0));
+        assert!(freeze.start(1, 3));
+        assert!(!freeze.start(0, 3));
+        assert_eq!(freeze.owner(), 1);
+    }
+
+    #[test]
+    fn ticks_down() {
+        let mut freeze = SuperFreeze::NEW;
+        freeze.start(1, 2);
+        freeze.set_zoomed();
+        assert!(!freeze.tick());
+        assert_eq!(freeze.frames_left(), 1);
+        assert!(freeze.tick());
+        assert!(!freeze.is_active());
+        assert_eq!(freeze.owner(), -1);
+        assert!(freeze.is_zoomed());
+        assert!(!freeze.tick());
+    }
+
+    #[test]
+    fn stops_early() {
+        let mut freeze = SuperFreeze::NEW;
+        assert!(!freeze.stop());
+        freeze.start(2, 30);
+        assert!(freeze.stop());
+        assert!(!freeze.is_active());
+        assert!(freeze.start(0, 5));
+        assert!(!freeze.is_zoomed());
+    }
+}
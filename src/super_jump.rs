@@ -0,0 +1,87 @@
+use crate::vars::PLAYER_MAX;
+
+/// Per-fighter settings for super jumps. Set through FGCModule::set_super_jump_config.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SuperJumpConfig {
+    pub enabled: bool,
+    /// How many frames after holding down you can still input up to super jump.
+    pub window: f32,
+    /// How much higher a super jump goes than a normal jump. 2.0 goes twice as high.
+    pub height_mul: f32,
+    /// How much faster a super jump moves horizontally than a normal jump.
+    pub speed_x_mul: f32,
+    /// Whether double jumps are still allowed after a super jump.
+    pub allow_jump_aerial: bool,
+    /// Whether air dashes (and air dodges) are still allowed after a super jump.
+    pub allow_air_dash: bool
+}
+
+impl SuperJumpConfig {
+    pub const DISABLED : SuperJumpConfig = SuperJumpConfig {
+        enabled: false,
+        window: 10.0,
+        height_mul: 1.5,
+        speed_x_mul: 1.0,
+        allow_jump_aerial: false,
+        allow_air_dash: true
+    };
+
+    /// The multiplier to apply to the jump's vertical speed so it reaches height_mul times the height.
+    pub fn speed_y_mul(&self) -> f32 {
+        self.height_mul.max(0.0).sqrt()
+    }
+}
+
+impl Default for SuperJumpConfig {
+    fn default() -> Self {
+        SuperJumpConfig::DISABLED
+    }
+}
+
+/// Checks if a numpad direction is held down (1, 2 or 3).
+pub fn is_down_direction(dir: i32) -> bool {
+    (1..=3).contains(&dir)
+}
+
+/// Checks if a numpad direction is held up (7, 8 or 9).
+pub fn is_up_direction(dir: i32) -> bool {
+    (7..=9).contains(&dir)
+}
+
+/// Super jump settings for each player.
+pub static mut SUPER_JUMP_CONFIGS : [SuperJumpConfig; PLAYER_MAX] = [SuperJumpConfig::DISABLED; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(height_mul: f32) -> SuperJumpConfig {
+        SuperJumpConfig { enabled: true, height_mul, ..SuperJumpConfig::DISABLED }
+    }
+
+    #[test]
+    fn jump_height_grows_with_the_square_of_the_speed() {
+        // A jump's height is v^2 / 2g, so reaching height_mul times the height needs sqrt(height_mul) times the speed.
+        for &height_mul in &[1.0, 1.5, 2.0, 4.0] {
+            let speed_y_mul = config(height_mul).speed_y_mul();
+            assert!((speed_y_mul * speed_y_mul - height_mul).abs() < 1e-5);
+        }
+        assert_eq!(config(4.0).speed_y_mul(), 2.0);
+        assert_eq!(config(1.0).speed_y_mul(), 1.0);
+    }
+
+    #[test]
+    fn negative_heights_dont_jump() {
+        assert_eq!(config(-1.0).speed_y_mul(), 0.0);
+    }
+
+    #[test]
+    fn down_and_up_directions() {
+        let down : Vec<i32> = (1..=9).filter(|&dir| is_down_direction(dir)).collect();
+        let up : Vec<i32> = (1..=9).filter(|&dir| is_up_direction(dir)).collect();
+        assert_eq!(down, [1, 2, 3]);
+        assert_eq!(up, [7, 8, 9]);
+        assert!(!is_down_direction(5) && !is_up_direction(5));
+        assert!(!is_down_direction(0) && !is_up_direction(10));
+    }
+}
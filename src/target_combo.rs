@@ -0,0 +1,119 @@
+use crate::vars::PLAYER_MAX;
+
+/// A target combo: a specific string of moves followed by an input, which leads into a unique motion.
+/// The new motion plays within the current status, rather than changing status.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TargetCombo {
+    /// The motion kinds that need to have been used, in order, with the last one being the motion you're currently in.
+    pub moves: Vec<u64>,
+    /// The cat1 flags that finish the target combo (*FIGHTER_PAD_CMD_CAT1_FLAG_XXXXXX).
+    pub cat1: i32,
+    /// The motion kind the target combo leads into.
+    pub motion: u64
+}
+
+/// A fighter's target combos, along with the motions used in the current string.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TargetComboSet {
+    pub combos: Vec<TargetCombo>,
+    history: Vec<u64>
+}
+
+impl TargetComboSet {
+    pub const NEW : TargetComboSet = TargetComboSet {
+        combos: Vec::new(),
+        history: Vec::new()
+    };
+
+    /// Adds a motion to the current string. Does nothing if it's already the latest motion in the string.
+    pub fn push_motion(&mut self, motion: u64) {
+        if self.history.last() != Some(&motion) {
+            self.history.push(motion);
+        }
+    }
+
+    /// Ends the current string.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Returns the motions used in the current string, in order.
+    pub fn history(&self) -> &[u64] {
+        &self.history
+    }
+
+    /// Finds the target combo that matches the current string and the cat1 flags.
+    /// If more than one matches, the one with the longest string wins.
+    pub fn find_match(&self, cat1: i32) -> Option<&TargetCombo> {
+        self.combos.iter()
+            .filter(|combo| combo.cat1 & cat1 != 0
+                && !combo.moves.is_empty()
+                && self.history.ends_with(&combo.moves))
+            .max_by_key(|combo| combo.moves.len())
+    }
+}
+
+/// The target combos for each player.
+pub static mut TARGET_COMBOS : [TargetComboSet; PLAYER_MAX] = [TargetComboSet::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JAB : u64 = 1;
+    const TILT : u64 = 2;
+    const SMASH : u64 = 3;
+    const ATTACK_N : i32 = 0b01;
+    const ATTACK_S : i32 = 0b10;
+
+    fn combo(moves: &[u64], cat1: i32, motion: u64) -> TargetCombo {
+        TargetCombo { moves: moves.to_vec(), cat1, motion }
+    }
+
+    fn set(combos: Vec<TargetCombo>, history: &[u64]) -> TargetComboSet {
+        let mut set = TargetComboSet { combos, ..TargetComboSet::NEW };
+        for &motion in history {
+            set.push_motion(motion);
+        }
+        set
+    }
+
+    #[test]
+    fn the_longest_matching_string_wins() {
+        let combos = vec![
+            combo(&[TILT], ATTACK_N, 10),
+            combo(&[JAB, TILT], ATTACK_N, 11)
+        ];
+        assert_eq!(set(combos.clone(), &[JAB, TILT]).find_match(ATTACK_N).map(|c| c.motion), Some(11));
+        assert_eq!(set(combos, &[SMASH, TILT]).find_match(ATTACK_N).map(|c| c.motion), Some(10));
+    }
+
+    #[test]
+    fn only_the_start_of_a_string_doesnt_match() {
+        let combos = vec![combo(&[JAB, TILT, SMASH], ATTACK_N, 10)];
+        assert_eq!(set(combos.clone(), &[JAB, TILT]).find_match(ATTACK_N), None);
+        assert_eq!(set(combos, &[TILT, JAB, TILT, SMASH]).find_match(ATTACK_N).map(|c| c.motion), Some(10));
+    }
+
+    #[test]
+    fn the_wrong_input_misses() {
+        let combos = vec![combo(&[JAB], ATTACK_S, 10)];
+        assert_eq!(set(combos.clone(), &[JAB]).find_match(ATTACK_N), None);
+        assert_eq!(set(combos, &[JAB]).find_match(ATTACK_N | ATTACK_S).map(|c| c.motion), Some(10));
+    }
+
+    #[test]
+    fn moves_have_to_be_used_in_order() {
+        let combos = vec![combo(&[JAB, TILT], ATTACK_N, 10)];
+        assert_eq!(set(combos.clone(), &[TILT, JAB]).find_match(ATTACK_N), None);
+        assert_eq!(set(combos, &[JAB, JAB, TILT, TILT]).find_match(ATTACK_N).map(|c| c.motion), Some(10));
+    }
+
+    #[test]
+    fn clearing_the_history_ends_the_string() {
+        let mut set = set(vec![combo(&[JAB], ATTACK_N, 10)], &[JAB, JAB]);
+        assert_eq!(set.history(), &[JAB]);
+        set.clear_history();
+        assert_eq!(set.find_match(ATTACK_N), None);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::vars::PLAYER_MAX;
+
+/// Settings for breaking throws in Fighting Game Mode.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ThrowTechConfig {
+    pub enabled: bool,
+    /// How many frames after being grabbed you have to press grab and break the throw.
+    pub window: i32,
+    /// If grab was pressed within this many frames before being grabbed, the tech is locked out,
+    /// so you can't mash grab or option-select a tech with your own grab.
+    pub lockout: i32,
+    /// How fast both fighters get pushed away from each other after a throw break.
+    pub pushback: f32
+}
+
+impl ThrowTechConfig {
+    pub const DEFAULT : ThrowTechConfig = ThrowTechConfig {
+        enabled: true,
+        window: 7,
+        lockout: 10,
+        pushback: 1.5
+    };
+}
+
+impl Default for ThrowTechConfig {
+    fn default() -> Self {
+        ThrowTechConfig::DEFAULT
+    }
+}
+
+/// Keeps track of grab presses and how long you've been grabbed for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ThrowTechState {
+    grabbed_frame: i32,
+    frames_since_press: i32,
+    locked: bool
+}
+
+impl ThrowTechState {
+    pub const NEW : ThrowTechState = ThrowTechState {
+        grabbed_frame: -1,
+        frames_since_press: i32::MAX,
+        locked: false
+    };
+
+    /// Records this frame's grab input. Should be called once per frame, whether you're grabbed or not.
+    /// Returns true on the frame the throw gets broken.
+    /// # Arguments
+    ///
+    /// * `grabbed` - If you're being held by a grab.
+    /// * `grab_pressed` - If grab was pressed this frame.
+    pub fn update(&mut self, config: &ThrowTechConfig, grabbed: bool, grab_pressed: bool) -> bool {
+        let frames_since_press = self.frames_since_press;
+        self.frames_since_press = if grab_pressed { 0 } else { frames_since_press.saturating_add(1) };
+        if !grabbed {
+            self.grabbed_frame = -1;
+            self.locked = false;
+            return false;
+        }
+        if self.grabbed_frame < 0 {
+            self.grabbed_frame = 0;
+            self.locked = frames_since_press < config.lockout;
+        }
+        else {
+            self.grabbed_frame += 1;
+        }
+        if !grab_pressed
+        || !config.enabled {
+            return false;
+        }
+        if self.locked
+        || self.grabbed_frame >= config.window {
+            self.locked = true;
+            return false;
+        }
+        self.locked = true;
+        true
+    }
+}
+
+impl Default for ThrowTechState {
+    fn default() -> Self {
+        ThrowTechState::NEW
+    }
+}
+
+/// The throw tech settings for each player.
+pub static mut THROW_TECH_CONFIGS : [ThrowTechConfig; PLAYER_MAX] = [ThrowTechConfig::DEFAULT; PLAYER_MAX];
+
+/// The throw tech state for each player.
+pub static mut THROW_TECH_STATES : [ThrowTechState; PLAYER_MAX] = [ThrowTechState::NEW; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG : ThrowTechConfig = ThrowTechConfig::DEFAULT;
+
+    /// Runs `frames` frames of the same input, returning true if the throw got broken on any of them.
+    fn hold(state: &mut ThrowTechState, frames: i32, grabbed: bool, grab_pressed: bool) -> bool {
+        (0..frames).fold(false, |broken, _| state.update(&CONFIG, grabbed, grab_pressed) || broken)
+    }
+
+    #[test]
+    fn breaks_inside_the_window() {
+        let mut state = ThrowTechState::NEW;
+        hold(&mut state, CONFIG.lockout, false, false);
+        assert!(!hold(&mut state, CONFIG.window - 1, true, false));
+        assert!(state.update(&CONFIG, true, true));
+        assert!(!state.update(&CONFIG, true, true));
+    }
+
+    #[test]
+    fn late_presses_dont_break() {
+        let mut state = ThrowTechState::NEW;
+        hold(&mut state, CONFIG.window, true, false);
+        assert!(!hold(&mut state, 2, true, true));
+    }
+
+    #[test]
+    fn pressing_before_the_grab_locks_out() {
+        let mut state = ThrowTechState::NEW;
+        state.update(&CONFIG, false, true);
+        hold(&mut state, CONFIG.lockout - 2, false, false);
+        assert!(!state.update(&CONFIG, true, false));
+        assert!(!state.update(&CONFIG, true, true));
+
+        let mut state = ThrowTechState::NEW;
+        state.update(&CONFIG, false, true);
+        hold(&mut state, CONFIG.lockout, false, false);
+        assert!(state.update(&CONFIG, true, true));
+    }
+
+    #[test]
+    fn being_released_resets_the_window() {
+        let mut state = ThrowTechState::NEW;
+        hold(&mut state, CONFIG.lockout, false, false);
+        hold(&mut state, CONFIG.window + 5, true, false);
+        hold(&mut state, CONFIG.lockout, false, false);
+        assert!(state.update(&CONFIG, true, true));
+    }
+
+    #[test]
+    fn disabled_never_breaks() {
+        let config = ThrowTechConfig { enabled: false, ..CONFIG };
+        let mut state = ThrowTechState::NEW;
+        assert!(!state.update(&config, true, true));
+    }
+}
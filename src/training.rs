@@ -0,0 +1,375 @@
+use crate::{
+    vars::PLAYER_MAX,
+    cpu::CpuRng
+};
+
+/// How many recording slots there are.
+pub const RECORDING_SLOT_MAX : usize = 4;
+
+/// How many frames the dummy needs to go without being hit before After First Hit stops blocking.
+pub const GUARD_AFTER_HIT_RESET_FRAMES : i32 = 30;
+
+/// When the training dummy blocks.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GuardMode {
+    None,
+    All,
+    /// Blocks everything after getting hit, until it's left alone for a while.
+    AfterFirstHit,
+    /// Decides whether to block each attack at random.
+    Random
+}
+
+impl GuardMode {
+    pub fn next(self) -> GuardMode {
+        match self {
+            GuardMode::None => GuardMode::All,
+            GuardMode::All => GuardMode::AfterFirstHit,
+            GuardMode::AfterFirstHit => GuardMode::Random,
+            GuardMode::Random => GuardMode::None
+        }
+    }
+}
+
+/// What the training dummy does when it lands while launched.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TechOption {
+    /// Misses the tech.
+    None,
+    InPlace,
+    Roll,
+    /// Picks one of the other options at random.
+    Random
+}
+
+impl TechOption {
+    pub fn next(self) -> TechOption {
+        match self {
+            TechOption::None => TechOption::InPlace,
+            TechOption::InPlace => TechOption::Roll,
+            TechOption::Roll => TechOption::Random,
+            TechOption::Random => TechOption::None
+        }
+    }
+}
+
+/// What the training dummy does as soon as it can act after getting up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Reversal {
+    None,
+    /// Changes into TrainingSettings::reversal_status.
+    Status,
+    /// Plays back the recording in the given slot.
+    Recording(usize)
+}
+
+impl Reversal {
+    pub fn next(self) -> Reversal {
+        match self {
+            Reversal::None => Reversal::Status,
+            Reversal::Status => Reversal::Recording(0),
+            Reversal::Recording(slot) if slot + 1 < RECORDING_SLOT_MAX => Reversal::Recording(slot + 1),
+            Reversal::Recording(_) => Reversal::None
+        }
+    }
+}
+
+/// The settings that can be changed with a button combo during training.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TrainingSetting {
+    Guard,
+    Tech,
+    Reversal,
+    CounterHit
+}
+
+/// How the training dummy behaves. Only used while FGC_TRAINING is on.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TrainingSettings {
+    pub guard: GuardMode,
+    /// How close (in units) an attacking opponent needs to be before the dummy blocks.
+    pub guard_range: f32,
+    pub tech: TechOption,
+    pub reversal: Reversal,
+    /// The status used by Reversal::Status.
+    pub reversal_status: i32,
+    /// If true, every hit on the dummy counts as a counter-hit.
+    pub counter_hit: bool
+}
+
+impl TrainingSettings {
+    pub const DEFAULT : TrainingSettings = TrainingSettings {
+        guard: GuardMode::None,
+        guard_range: 40.0,
+        tech: TechOption::None,
+        reversal: Reversal::None,
+        reversal_status: 0,
+        counter_hit: false
+    };
+
+    /// Moves a setting on to its next option.
+    pub fn cycle(&mut self, setting: TrainingSetting) {
+        match setting {
+            TrainingSetting::Guard => self.guard = self.guard.next(),
+            TrainingSetting::Tech => self.tech = self.tech.next(),
+            TrainingSetting::Reversal => self.reversal = self.reversal.next(),
+            TrainingSetting::CounterHit => self.counter_hit = !self.counter_hit
+        }
+    }
+}
+
+impl Default for TrainingSettings {
+    fn default() -> Self {
+        TrainingSettings::DEFAULT
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RecordedStatus {
+    pub frame: i32,
+    pub status: i32
+}
+
+/// A list of statuses and the frames they were changed into, counted from the start of the recording.
+/// Only common statuses should be recorded, since fighter-specific status kinds mean something else on every fighter.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Recording {
+    pub statuses: Vec<RecordedStatus>
+}
+
+impl Recording {
+    pub const NEW : Recording = Recording {
+        statuses: Vec::new()
+    };
+
+    pub fn record(&mut self, frame: i32, status: i32) {
+        self.statuses.push(RecordedStatus { frame, status });
+    }
+
+    /// Returns the status that was changed into on the given frame, if there was one.
+    pub fn status_at(&self, frame: i32) -> Option<i32> {
+        self.statuses.iter()
+            .find(|recorded| recorded.frame == frame)
+            .map(|recorded| recorded.status)
+    }
+
+    /// Returns the frame of the last recorded status.
+    pub fn length(&self) -> i32 {
+        self.statuses.last().map_or(0, |recorded| recorded.frame)
+    }
+}
+
+/// What the training dummy decided to do when landing while launched.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TechChoice {
+    InPlace,
+    Roll
+}
+
+/// Everything the training dummy needs to remember between frames.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DummyState {
+    rng: CpuRng,
+    was_hit: bool,
+    safe_frames: i32,
+    threatened: bool,
+    will_guard: bool,
+    /// Set while the dummy is knocked down, so it knows to do its reversal once it can act.
+    pub waking_up: bool,
+    tech: Option<Option<TechChoice>>,
+    playback: Option<(usize, i32)>
+}
+
+impl DummyState {
+    pub const NEW : DummyState = DummyState {
+        rng: CpuRng::new(0),
+        was_hit: false,
+        safe_frames: 0,
+        threatened: false,
+        will_guard: false,
+        waking_up: false,
+        tech: None,
+        playback: None
+    };
+
+    /// Decides if the dummy blocks this frame. Should be called once per frame.
+    /// # Arguments
+    ///
+    /// * `threatened` - If an opponent is attacking nearby.
+    /// * `damaged` - If the dummy is currently being hit.
+    pub fn guard_step(&mut self, mode: GuardMode, threatened: bool, damaged: bool) -> bool {
+        if damaged {
+            self.was_hit = true;
+            self.safe_frames = 0;
+        }
+        else if !threatened {
+            self.safe_frames += 1;
+            if self.safe_frames >= GUARD_AFTER_HIT_RESET_FRAMES {
+                self.was_hit = false;
+            }
+        }
+        if threatened && !self.threatened {
+            self.will_guard = self.rng.chance(0.5);
+        }
+        self.threatened = threatened;
+        if !threatened || damaged {
+            return false;
+        }
+        match mode {
+            GuardMode::None => false,
+            GuardMode::All => true,
+            GuardMode::AfterFirstHit => self.was_hit,
+            GuardMode::Random => self.will_guard
+        }
+    }
+
+    /// Decides how the dummy techs, or None if it shouldn't.
+    pub fn tech_choice(&mut self, option: TechOption) -> Option<TechChoice> {
+        match option {
+            TechOption::None => None,
+            TechOption::InPlace => Some(TechChoice::InPlace),
+            TechOption::Roll => Some(TechChoice::Roll),
+            TechOption::Random => match self.rng.pick(3) {
+                0 => None,
+                1 => Some(TechChoice::InPlace),
+                _ => Some(TechChoice::Roll)
+            }
+        }
+    }
+
+    /// Decides how the dummy techs once per launch, and returns that choice on the frame the game lets it tech.
+    /// Should be called once per frame.
+    /// # Arguments
+    ///
+    /// * `launched` - If the dummy is flying from a hit.
+    /// * `can_tech` - If the game would let the dummy tech right now.
+    pub fn tech_step(&mut self, option: TechOption, launched: bool, can_tech: bool) -> Option<TechChoice> {
+        if !launched {
+            self.tech = None;
+            return None;
+        }
+        let choice = match self.tech {
+            Some(choice) => choice,
+            None => {
+                let choice = self.tech_choice(option);
+                self.tech = Some(choice);
+                choice
+            }
+        };
+        if can_tech { choice } else { None }
+    }
+
+    /// Starts playing back the recording in the given slot.
+    pub fn start_playback(&mut self, slot: usize) {
+        if slot < RECORDING_SLOT_MAX {
+            self.playback = Some((slot, 0));
+        }
+    }
+
+    pub fn is_playing_back(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Returns the status to change into this frame, if the recording changed status on this frame,
+    /// then moves to the next frame. Should be called once per frame while playing back.
+    pub fn playback_step(&mut self, recordings: &[Recording]) -> Option<i32> {
+        let (slot, frame) = self.playback?;
+        let recording = recordings.get(slot)?;
+        if frame > recording.length() {
+            self.playback = None;
+            return None;
+        }
+        self.playback = Some((slot, frame + 1));
+        recording.status_at(frame)
+    }
+
+    pub fn stop_playback(&mut self) {
+        self.playback = None;
+    }
+}
+
+impl Default for DummyState {
+    fn default() -> Self {
+        DummyState::NEW
+    }
+}
+
+/// The training dummy's settings.
+pub static mut TRAINING_SETTINGS : TrainingSettings = TrainingSettings::DEFAULT;
+
+/// The training dummy's state for each player.
+pub static mut DUMMY_STATES : [DummyState; PLAYER_MAX] = [DummyState::NEW; PLAYER_MAX];
+
+/// The recordings that can be played back as a reversal.
+pub static mut RECORDINGS : [Recording; RECORDING_SLOT_MAX] = [Recording::NEW; RECORDING_SLOT_MAX];
+
+/// The slot currently being recorded into, and how many frames have been recorded so far.
+pub static mut RECORDING_STATE : Option<(usize, i32)> = None;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_cycle() {
+        let mut settings = TrainingSettings::DEFAULT;
+        settings.cycle(TrainingSetting::Guard);
+        assert_eq!(settings.guard, GuardMode::All);
+        settings.cycle(TrainingSetting::CounterHit);
+        assert!(settings.counter_hit);
+        let mut reversal = Reversal::None;
+        for _ in 0..RECORDING_SLOT_MAX + 2 {
+            reversal = reversal.next();
+        }
+        assert_eq!(reversal, Reversal::None);
+    }
+
+    #[test]
+    fn guard_after_first_hit() {
+        let mut dummy = DummyState::NEW;
+        assert!(!dummy.guard_step(GuardMode::AfterFirstHit, true, false));
+        dummy.guard_step(GuardMode::AfterFirstHit, false, true);
+        assert!(dummy.guard_step(GuardMode::AfterFirstHit, true, false));
+        for _ in 0..GUARD_AFTER_HIT_RESET_FRAMES {
+            dummy.guard_step(GuardMode::AfterFirstHit, false, false);
+        }
+        assert!(!dummy.guard_step(GuardMode::AfterFirstHit, true, false));
+    }
+
+    #[test]
+    fn techs_only_when_allowed() {
+        let mut dummy = DummyState::NEW;
+        assert_eq!(dummy.tech_step(TechOption::Roll, true, false), None);
+        assert_eq!(dummy.tech_step(TechOption::Roll, true, true), Some(TechChoice::Roll));
+        assert_eq!(dummy.tech_step(TechOption::Roll, false, true), None);
+        assert_eq!(dummy.tech_step(TechOption::None, true, true), None);
+    }
+
+    #[test]
+    fn random_tech_is_decided_once_per_launch() {
+        let mut dummy = DummyState::NEW;
+        dummy.tech_step(TechOption::Random, true, false);
+        let choice = dummy.tech;
+        for _ in 0..20 {
+            dummy.tech_step(TechOption::Random, true, false);
+            assert_eq!(dummy.tech, choice);
+        }
+        assert_eq!(dummy.tech_step(TechOption::Random, true, true), choice.unwrap());
+    }
+
+    #[test]
+    fn recording_plays_back() {
+        let mut recording = Recording::NEW;
+        recording.record(0, 5);
+        recording.record(3, 7);
+        let mut recordings = [Recording::NEW, recording];
+        let mut dummy = DummyState::NEW;
+        dummy.start_playback(1);
+        let played: Vec<Option<i32>> = (0..5).map(|_| dummy.playback_step(&recordings)).collect();
+        assert_eq!(played, [Some(5), None, None, Some(7), None]);
+        assert!(!dummy.is_playing_back());
+        recordings[1] = Recording::NEW;
+        dummy.start_playback(RECORDING_SLOT_MAX);
+        assert!(!dummy.is_playing_back());
+    }
+}
@@ -38,6 +38,8 @@ pub static NOTIFY_LOG_EVENT_COLLISION_HIT_SEARCH_CODE: &[u8] = &[
 
 // Common
 pub const ZERO_VECTOR : Vector3f = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
+/// The maximum number of players. Every per-player table is this long and indexed by entry ID (see WarkModule::player_slot).
+pub const PLAYER_MAX : usize = 8;
 
 // System Mechanics
 pub const FIGHTER_INSTANCE_WORK_ID_FLAG_GUARD_OFF_ATTACK_CANCEL : i32 = 0x20000116;
@@ -50,11 +52,25 @@ pub const FIGHTER_INSTANCE_WORK_ID_FLAG_IS_FGC : i32 = 0x2000011C;
 pub const FIGHTER_INSTANCE_WORK_ID_FLAG_CANCEL_ESCAPE_TO_ESCAPE_FB : i32 = 0x2000011D;
 pub const FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP : i32 = 0x2000011E;
 pub const FIGHTER_INSTANCE_WORK_ID_FLAG_FORCE_ESCAPE_AIR_SLIDE : i32 = 0x2000011F;
+pub const FIGHTER_INSTANCE_WORK_ID_FLAG_KARA_CANCEL : i32 = 0x20000120;
+pub const FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH : i32 = 0x20000121;
+pub const FIGHTER_INSTANCE_WORK_ID_FLAG_STEP_DASH : i32 = 0x20000122;
+pub const FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_CLING : i32 = 0x20000123;
+pub const FIGHTER_INSTANCE_WORK_ID_FLAG_UNBLOCKABLE_THROW : i32 = 0x20000124;
+pub const FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_RUN : i32 = 0x20000125;
+/// Turns on the training dummy, set up with FGCModule::set_training_settings.
 pub static mut FGC_TRAINING : bool = false;
 
 pub const FIGHTER_INSTANCE_WORK_ID_INT_TARGET_ID : i32 = 0x100000ED;
 pub const FIGHTER_INSTANCE_WORK_ID_INT_USED_GROUND_NORMALS : i32 = 0x100000EE;
 pub const FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS : i32 = 0x100000EF;
+pub const FIGHTER_INSTANCE_WORK_ID_INT_LAST_AERIAL : i32 = 0x100000F8;
+pub const FIGHTER_INSTANCE_WORK_ID_INT_AERIAL_STRING_LENGTH : i32 = 0x100000F9;
+pub const FIGHTER_INSTANCE_WORK_ID_INT_LAST_GROUND_NORMAL : i32 = 0x100000FA;
+pub const FIGHTER_INSTANCE_WORK_ID_INT_GROUND_NORMAL_STRING_LENGTH : i32 = 0x100000FB;
+pub const FIGHTER_INSTANCE_WORK_ID_INT_AIR_DASH_COUNT : i32 = 0x100000FC;
+pub const FIGHTER_INSTANCE_WORK_ID_INT_WALL_JUMP_COUNT : i32 = 0x100000FD;
+pub const FIGHTER_INSTANCE_WORK_ID_INT_WALL_RUN_FRAME : i32 = 0x100000FE;
 // pub const FIGHTER_INSTANCE_WORK_ID_INT_CUSTOM_COMMAND_236_STEP : i32 = 0x100000F0;
 // pub const FIGHTER_INSTANCE_WORK_ID_INT_CUSTOM_COMMAND_236_TIMER : i32 = 0x100000F1;
 // pub const FIGHTER_INSTANCE_WORK_ID_INT_CUSTOM_COMMAND_214_STEP : i32 = 0x100000F2;
@@ -67,12 +83,19 @@ pub const FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS : i32 = 0x100000EF;
 pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_DAMAGE_PREV : i32 = 0x5F;
 pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_FLICK_DOWN : i32 = 0x60;
 pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_SUPER_JUMP_FRAME : i32 = 0x61;
+pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_KARA_SPEED_X : i32 = 0x62;
+pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_KARA_SPEED_Y : i32 = 0x63;
+pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_DIR_X : i32 = 0x64;
+pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_DIR_Y : i32 = 0x65;
+pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_THROW_TECH_PUSHBACK : i32 = 0x66;
+pub const FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_FRAME : i32 = 0x67;
 
 pub const FIGHTER_STATUS_GUARD_OFF_WORK_INT_ATTACK_CANCEL_FRAME : i32 = 0x1100000F;
 
 pub const FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_HOLD : i32 = 0x2100000D;
 pub const FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_LOOP : i32 = 0x2100000E;
 pub const FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_ENABLE_ACTION : i32 = 0x2100000F;
+pub const FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_BUFF_GIVEN : i32 = 0x21000010;
 
 pub const FIGHTER_STATUS_APPEAL_WORK_INT_APPEAL_LOOP_MOT : i32 = 0x11000007;
 pub const FIGHTER_STATUS_APPEAL_WORK_INT_APPEAL_HELD_BUTTON : i32 = 0x11000008;
@@ -94,13 +117,21 @@ pub const ATTACK_AIR_B_MASK : i32 = 0b00100;
 pub const ATTACK_AIR_HI_MASK : i32 = 0b01000;
 pub const ATTACK_AIR_LW_MASK : i32 = 0b10000;
 
-pub const ATTACK_N_MASK : i32 = 0b0000001;
-pub const ATTACK_S3_MASK : i32 = 0b0000010;
-pub const ATTACK_HI3_MASK : i32 = 0b0000100;
-pub const ATTACK_LW3_MASK : i32 = 0b0001000;
-pub const ATTACK_S4_MASK : i32 = 0b0010000;
-pub const ATTACK_HI4_MASK : i32 = 0b0100000;
-pub const ATTACK_LW4_MASK : i32 = 0b1000000;
+pub const ATTACK_N_MASK : i32 = 0b00000001;
+pub const ATTACK_S3_MASK : i32 = 0b00000010;
+pub const ATTACK_HI3_MASK : i32 = 0b00000100;
+pub const ATTACK_LW3_MASK : i32 = 0b00001000;
+pub const ATTACK_S4_MASK : i32 = 0b00010000;
+pub const ATTACK_HI4_MASK : i32 = 0b00100000;
+pub const ATTACK_LW4_MASK : i32 = 0b01000000;
+pub const ATTACK_DASH_MASK : i32 = 0b10000000;
+
+// Used to pick when a cancel route is allowed. Combine them to allow a cancel in more than one case,
+// for example CANCEL_ON_HIT | CANCEL_ON_BLOCK for a cancel that works on contact.
+pub const CANCEL_ON_HIT : i32 = 0b001;
+pub const CANCEL_ON_BLOCK : i32 = 0b010;
+pub const CANCEL_ON_WHIFF : i32 = 0b100;
+pub const CANCEL_ON_ALL : i32 = 0b111;
 
 pub const FIGHTER_STATUS_WORK_ID_FLAG_JUMP_CANCEL : i32 = 0x2100002B;
 // pub const FIGHTER_STATUS_WORK_ID_FLAG_DASH_CANCEL : i32 = 0x2100002C;
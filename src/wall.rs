@@ -0,0 +1,108 @@
+use crate::vars::PLAYER_MAX;
+
+/// Which inputs perform a wall jump.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WallJumpInput {
+    /// Dashing away from the wall.
+    AwayFromWall,
+    /// Pressing jump.
+    Jump,
+    /// Either of the above.
+    Either
+}
+
+impl WallJumpInput {
+    /// Checks if the inputs made this frame should perform a wall jump.
+    pub fn is_input(&self, away_from_wall: bool, jump: bool) -> bool {
+        match self {
+            WallJumpInput::AwayFromWall => away_from_wall,
+            WallJumpInput::Jump => jump,
+            WallJumpInput::Either => away_from_wall || jump
+        }
+    }
+}
+
+/// Per-fighter settings for wall mechanics. Set through FGCModule::set_wall_config.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct WallConfig {
+    pub enabled: bool,
+    pub input: WallJumpInput,
+    /// How many wall jumps you get before landing, or 0 for no limit.
+    pub wall_jump_max: i32,
+    /// Lets you hold towards a wall to slide down it slowly.
+    pub cling: bool,
+    /// The fastest you can fall while clinging to a wall.
+    pub cling_slide_speed: f32,
+    /// Lets you hold up towards a wall to run up it.
+    pub wall_run: bool,
+    /// How fast you move up the wall while wall running.
+    pub wall_run_speed: f32,
+    /// How many frames of wall running you get before landing.
+    pub wall_run_frames: i32
+}
+
+impl WallConfig {
+    /// Matches how MiscModule::wall_jump_check has always behaved.
+    pub const DEFAULT : WallConfig = WallConfig {
+        enabled: true,
+        input: WallJumpInput::Either,
+        wall_jump_max: 0,
+        cling: false,
+        cling_slide_speed: 0.5,
+        wall_run: false,
+        wall_run_speed: 1.5,
+        wall_run_frames: 20
+    };
+
+    /// Checks if you still have a wall jump left.
+    pub fn can_wall_jump(&self, count: i32) -> bool {
+        self.enabled && (self.wall_jump_max <= 0 || count < self.wall_jump_max)
+    }
+
+    /// Checks if you still have wall running left, given how many frames of it you've used since landing.
+    pub fn can_wall_run(&self, frames_used: i32) -> bool {
+        self.enabled && self.wall_run && frames_used < self.wall_run_frames
+    }
+}
+
+impl Default for WallConfig {
+    fn default() -> Self {
+        WallConfig::DEFAULT
+    }
+}
+
+/// Wall settings for each player.
+pub static mut WALL_CONFIGS : [WallConfig; PLAYER_MAX] = [WallConfig::DEFAULT; PLAYER_MAX];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wall_jump_inputs() {
+        assert!(WallJumpInput::AwayFromWall.is_input(true, false));
+        assert!(!WallJumpInput::AwayFromWall.is_input(false, true));
+        assert!(WallJumpInput::Jump.is_input(false, true));
+        assert!(WallJumpInput::Either.is_input(true, false));
+        assert!(!WallJumpInput::Either.is_input(false, false));
+    }
+
+    #[test]
+    fn wall_jump_limit() {
+        let config = WallConfig::DEFAULT;
+        assert!(config.can_wall_jump(100));
+        let config = WallConfig { wall_jump_max: 2, ..WallConfig::DEFAULT };
+        assert!(config.can_wall_jump(1));
+        assert!(!config.can_wall_jump(2));
+        let config = WallConfig { enabled: false, ..WallConfig::DEFAULT };
+        assert!(!config.can_wall_jump(0));
+    }
+
+    #[test]
+    fn wall_run_limit() {
+        assert!(!WallConfig::DEFAULT.can_wall_run(0));
+        let config = WallConfig { wall_run: true, ..WallConfig::DEFAULT };
+        assert!(config.can_wall_run(19));
+        assert!(!config.can_wall_run(20));
+    }
+}
@@ -1,7 +1,7 @@
 use {
     smash::{
         lua2cpp::L2CFighterCommon,
-        phx::Hash40,
+        phx::{Hash40, Vector3f},
         app::{lua_bind::*, *},
         lib::{lua_const::*, L2CValue}
     },
@@ -9,7 +9,29 @@ use {
     crate::{
         vars::*,
         table_const::*,
-        cancels::*
+        cancels::*,
+        cancel_routes::*,
+        stick::*,
+        buffer::*,
+        guard::*,
+        gatling::*,
+        target_combo::*,
+        super_jump::*,
+        air_dash::*,
+        wall::*,
+        appeal::*,
+        cinematic::*,
+        super_freeze::*,
+        cpu::*,
+        training::*,
+        input_display::*,
+        frame_data::*,
+        counter_hit::*,
+        stun::*,
+        throw_tech::*,
+        pushback::*,
+        round::*,
+        round_timer::*
     }
 };
 
@@ -49,6 +71,41 @@ pub mod WarkModule {
         WorkModule::set_float(module_accessor, counter, flag);
     }
 
+    /// Returns the entry ID of a fighter, for use with the per-player tables.
+    pub unsafe fn get_entry_id(module_accessor: *mut BattleObjectModuleAccessor) -> usize {
+        WorkModule::get_int(module_accessor, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as usize
+    }
+
+    /// Gets this fighter's slot in one of the per-player tables, or None if their entry ID doesn't fit in it.
+    pub unsafe fn player_slot<T>(table: &mut [T; PLAYER_MAX], module_accessor: *mut BattleObjectModuleAccessor) -> Option<&mut T> {
+        table.get_mut(get_entry_id(module_accessor))
+    }
+
+    /// Gets the module accessor of the fighter with the given entry ID.
+    /// Returns a null pointer if nobody is using that entry ID, since entry IDs don't have to be contiguous.
+    pub unsafe fn get_module_accessor_from_entry_id(entry_id: i32) -> *mut BattleObjectModuleAccessor {
+        if entry_id < 0
+        || entry_id as usize >= PLAYER_MAX {
+            return std::ptr::null_mut();
+        }
+        let object_id = Fighter::get_id_from_entry_id(entry_id);
+        if !sv_battle_object::is_active(object_id) {
+            return std::ptr::null_mut();
+        }
+        sv_battle_object::module_accessor(object_id)
+    }
+
+    /// Checks if a fighter is using the given entry ID.
+    pub unsafe fn is_entry_active(entry_id: usize) -> bool {
+        !get_module_accessor_from_entry_id(entry_id as i32).is_null()
+    }
+
+    /// Checks if this fighter has the lowest entry ID in use. Things that have to happen once per frame for the whole game,
+    /// rather than once per fighter, are driven by this fighter so they only happen once.
+    pub unsafe fn is_frame_owner(module_accessor: *mut BattleObjectModuleAccessor) -> bool {
+        (0..PLAYER_MAX).find(|&id| is_entry_active(id)) == Some(get_entry_id(module_accessor))
+    }
+
     pub unsafe fn is_operation_cpu(module_accessor: *mut BattleObjectModuleAccessor) -> bool {
         if utility::get_category(&mut *module_accessor) != *BATTLE_OBJECT_CATEGORY_FIGHTER {
             return false;
@@ -64,34 +121,82 @@ pub mod WarkModule {
 pub mod FGCModule {
     use super::*;
 
+    /// Returns how the current move has made contact, as a combination of CANCEL_ON_HIT and CANCEL_ON_BLOCK,
+    /// or CANCEL_ON_WHIFF if it hasn't made contact at all.
+    /// Returns 0 during hitlag, and after contact, also on the frame of contact or when FIGHTER_STATUS_WORK_ID_FLOAT_CANCEL_TIMER has run out.
+    /// Whiff cancels don't use the cancel timer, so limit them with a CancelWindow (see cancel_routes).
+    pub unsafe fn get_cancel_contact(fighter: &mut L2CFighterCommon) -> i32 {
+        cancel_contact(fighter, false)
+    }
+
+    /// Same as get_cancel_contact, but `contact_frame` allows cancels on the frame of contact, which air dash cancels have always allowed.
+    unsafe fn cancel_contact(fighter: &mut L2CFighterCommon, contact_frame: bool) -> i32 {
+        if fighter.global_table[IN_HITLAG].get_bool() {
+            return 0;
+        }
+        let mut contact = 0;
+        if AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_HIT) {
+            contact |= CANCEL_ON_HIT;
+        }
+        if AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_SHIELD) {
+            contact |= CANCEL_ON_BLOCK;
+        }
+        if contact == 0 {
+            return CANCEL_ON_WHIFF;
+        }
+        let cancel_timer = WorkModule::get_float(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_FLOAT_CANCEL_TIMER);
+        if cancel_timer <= 0.0
+        || (!contact_frame && AttackModule::is_infliction(fighter.module_accessor, *COLLISION_KIND_MASK_ALL)) {
+            return 0;
+        }
+        contact
+    }
+
+    /// Checks if a cancel route with the given condition (CANCEL_ON_HIT, CANCEL_ON_BLOCK, CANCEL_ON_WHIFF) is allowed right now.
+    pub unsafe fn is_cancel_condition(fighter: &mut L2CFighterCommon, condition: i32) -> bool {
+        get_cancel_contact(fighter) & condition != 0
+    }
+
     /// A function used to enable jump-cancels, styled after the special cancel functions that Ryu, Ken, and Terry use.
     pub unsafe fn jump_cancel_check_hit(fighter: &mut L2CFighterCommon, jump_on_block: bool) -> L2CValue {
-        let cancel_timer = WorkModule::get_float(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_FLOAT_CANCEL_TIMER);
-        if (AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_HIT)
-        || (AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_SHIELD) && jump_on_block))
-        && !AttackModule::is_infliction(fighter.module_accessor, *COLLISION_KIND_MASK_ALL)
-        && !fighter.global_table[IN_HITLAG].get_bool()
-        && cancel_timer > 0.0 {
+        let condition = if jump_on_block { CANCEL_ON_HIT | CANCEL_ON_BLOCK } else { CANCEL_ON_HIT };
+        jump_cancel_check_condition(fighter, condition)
+    }
+
+    /// Enables jump-cancels when the condition (CANCEL_ON_HIT, CANCEL_ON_BLOCK, CANCEL_ON_WHIFF) is met.
+    pub unsafe fn jump_cancel_check_condition(fighter: &mut L2CFighterCommon, condition: i32) -> L2CValue {
+        if is_cancel_condition(fighter, condition) {
             let sit = fighter.global_table[SITUATION_KIND].get_i32();
-            jump_cancel_common(fighter, sit.into())
-        }
-        else {
-            false.into()
+            if jump_cancel_common(fighter, sit.into()).get_bool() {
+                reset_aerial_string_on_jump(fighter);
+                return true.into();
+            }
         }
+        false.into()
     }
 
     /// A function used to enable jump-cancels, but it forces the jump-cancel regardless of if you hit anything.
     pub unsafe fn jump_cancel_check_exception(fighter: &mut L2CFighterCommon) -> L2CValue {
         let sit = fighter.global_table[SITUATION_KIND].get_i32();
-        jump_cancel_common(fighter, sit.into())
+        if jump_cancel_common(fighter, sit.into()).get_bool() {
+            reset_aerial_string_on_jump(fighter);
+            return true.into();
+        }
+        false.into()
     }
 
     /// Used to enable dash-cancels. You need to specify whether you're cancelling into a forward dash (reverse = false) or back dash (reverse = true);
     pub unsafe fn dash_cancel_check(fighter: &mut L2CFighterCommon, dash_on_block: bool, reverse: bool) -> L2CValue {
+        let condition = if dash_on_block { CANCEL_ON_HIT | CANCEL_ON_BLOCK } else { CANCEL_ON_HIT };
+        dash_cancel_check_condition(fighter, condition, reverse)
+    }
+
+    /// Enables dash-cancels when the condition (CANCEL_ON_HIT, CANCEL_ON_BLOCK, CANCEL_ON_WHIFF) is met.
+    /// You need to specify whether you're cancelling into a forward dash (reverse = false) or back dash (reverse = true);
+    pub unsafe fn dash_cancel_check_condition(fighter: &mut L2CFighterCommon, condition: i32, reverse: bool) -> L2CValue {
         let dir;
         let cat;
         let status;
-        let cancel_timer = WorkModule::get_float(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_FLOAT_CANCEL_TIMER);
         if reverse {
             dir = 4;
             cat = *FIGHTER_PAD_CMD_CAT1_FLAG_TURN_DASH;
@@ -102,11 +207,7 @@ pub mod FGCModule {
             cat = *FIGHTER_PAD_CMD_CAT1_FLAG_DASH;
             status = *FIGHTER_STATUS_KIND_DASH;
         }
-        if (AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_HIT)
-        || (AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_SHIELD) && dash_on_block))
-        && !AttackModule::is_infliction(fighter.module_accessor, *COLLISION_KIND_MASK_ALL)
-        && !fighter.global_table[IN_HITLAG].get_bool()
-        && cancel_timer > 0.0
+        if is_cancel_condition(fighter, condition)
         && ControlModule::get_command_flag_cat(fighter.module_accessor, 0) & cat != 0
         && get_command_stick_direction(fighter, true) == dir {
             StatusModule::change_status_request_from_script(fighter.module_accessor, status, true);
@@ -115,23 +216,262 @@ pub mod FGCModule {
         false.into()
     }
 
+    /// Records this frame's stick direction for double-tap detection. Call this once per frame.
+    pub unsafe fn update_dash_input(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id < PLAYER_MAX {
+            let dir = get_command_stick_direction(fighter, true);
+            DOUBLE_TAP_DIRS[entry_id] = TAP_TRACKERS[entry_id].update(dir);
+        }
+    }
+
+    /// Sets how many frames can pass between two taps for them to count as a double-tap.
+    pub unsafe fn set_double_tap_window(fighter: &mut L2CFighterCommon, window: u32) {
+        if let Some(slot) = WarkModule::player_slot(&mut TAP_TRACKERS, fighter.module_accessor) {
+            slot.window = window;
+        }
+    }
+
+    /// Returns the direction that was double-tapped this frame in numpad notation, relative to the way the fighter is facing.
+    /// Returns 5 if nothing was double-tapped.
+    pub unsafe fn get_double_tap_direction(fighter: &mut L2CFighterCommon) -> i32 {
+        WarkModule::player_slot(&mut DOUBLE_TAP_DIRS, fighter.module_accessor).copied().unwrap_or(5)
+    }
+
+    /// Enables dash-cancels in any direction when the condition (CANCEL_ON_HIT, CANCEL_ON_BLOCK, CANCEL_ON_WHIFF) is met.
+    /// On the ground, any forward direction (3, 6, 9) or back direction (1, 4, 7) works, and both the game's dash flags
+    /// and double-taps (see update_dash_input) are accepted. In the air, only a double-tap works, going into an air dash
+    /// if the fighter has one available (see is_air_dash_available). Air dodge inputs are left to air_dash_cancel_check_condition.
+    /// # Arguments
+    ///
+    /// * `kind` - Whether you cancel into a dash, a run, or a step dash.
+    /// * `forward` - Allows cancelling into a forward dash.
+    /// * `back` - Allows cancelling into a back dash.
+    pub unsafe fn dash_cancel_check_kind(fighter: &mut L2CFighterCommon, condition: i32, kind: DashKind, forward: bool, back: bool) -> L2CValue {
+        if !is_cancel_condition(fighter, condition) {
+            return false.into();
+        }
+        let tapped = get_double_tap_direction(fighter);
+        if fighter.global_table[SITUATION_KIND].get_i32() == *SITUATION_KIND_AIR {
+            if ((forward && tapped == 6) || (back && tapped == 4))
+            && is_air_dash_available(fighter) {
+                start_air_dash(fighter);
+                return true.into();
+            }
+            return false.into();
+        }
+        let cat1 = fighter.global_table[CMD_CAT1].get_i32();
+        let dir = get_command_stick_direction(fighter, true);
+        let is_forward = forward
+        && [3, 6, 9].contains(&dir)
+        && (cat1 & *FIGHTER_PAD_CMD_CAT1_FLAG_DASH != 0 || tapped == 6);
+        let is_back = back
+        && [1, 4, 7].contains(&dir)
+        && (cat1 & *FIGHTER_PAD_CMD_CAT1_FLAG_TURN_DASH != 0 || tapped == 4);
+        if !is_forward && !is_back {
+            return false.into();
+        }
+        let status = if is_forward {
+            if kind == DashKind::Run { *FIGHTER_STATUS_KIND_RUN } else { *FIGHTER_STATUS_KIND_DASH }
+        }
+        else {
+            *FIGHTER_STATUS_KIND_TURN_DASH
+        };
+        if kind == DashKind::StepDash {
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_STEP_DASH);
+        }
+        else {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_STEP_DASH);
+        }
+        StatusModule::change_status_request_from_script(fighter.module_accessor, status, true);
+        true.into()
+    }
+
     /// Used to check air dash cancels. This is set-up so you can only air dash, not air dodge.
     pub unsafe fn air_dash_cancel_check(fighter: &mut L2CFighterCommon, on_block: bool) -> L2CValue {
-        let cancel_timer = WorkModule::get_float(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_FLOAT_CANCEL_TIMER);
-        let sit = fighter.global_table[SITUATION_KIND].get_i32();
-        if (AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_HIT)
-        || (on_block && AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_SHIELD)))
-        && !fighter.global_table[IN_HITLAG].get_bool()
-        && cancel_timer > 0.0 {
-            if airdash_cancel_common(fighter, sit.into()).get_bool() {
-                WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_FORCE_ESCAPE_AIR_SLIDE);
+        let condition = if on_block { CANCEL_ON_HIT | CANCEL_ON_BLOCK } else { CANCEL_ON_HIT };
+        air_dash_cancel_check_condition(fighter, condition)
+    }
+
+    /// Enables air dash cancels when the condition (CANCEL_ON_HIT, CANCEL_ON_BLOCK, CANCEL_ON_WHIFF) is met.
+    /// If the fighter has an AirDashConfig, an air dodge input cancels into a real air dash (see start_air_dash) instead of a forced air dodge slide.
+    pub unsafe fn air_dash_cancel_check_condition(fighter: &mut L2CFighterCommon, condition: i32) -> L2CValue {
+        if cancel_contact(fighter, true) & condition == 0
+        || is_air_dash_blocked_by_super_jump(fighter) {
+            return false.into();
+        }
+        if get_air_dash_config(fighter).enabled {
+            if is_air_dash_available(fighter)
+            && fighter.global_table[CMD_CAT1].get_i32() & *FIGHTER_PAD_CMD_CAT1_FLAG_AIR_ESCAPE != 0 {
+                start_air_dash(fighter);
                 return true.into();
             }
+            return false.into();
+        }
+        let sit = fighter.global_table[SITUATION_KIND].get_i32();
+        if airdash_cancel_common(fighter, sit.into()).get_bool() {
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_FORCE_ESCAPE_AIR_SLIDE);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Sets the air dash settings for this fighter's player.
+    pub unsafe fn set_air_dash_config(fighter: &mut L2CFighterCommon, config: AirDashConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut AIR_DASH_CONFIGS, fighter.module_accessor) {
+            *slot = config;
+        }
+    }
+
+    /// Gets the air dash settings for this fighter's player.
+    pub unsafe fn get_air_dash_config(fighter: &mut L2CFighterCommon) -> AirDashConfig {
+        WarkModule::player_slot(&mut AIR_DASH_CONFIGS, fighter.module_accessor).copied().unwrap_or(AirDashConfig::DISABLED)
+    }
+
+    /// Checks if you have an air dash left and are high enough off the ground to use it, and that a super jump isn't blocking it.
+    pub unsafe fn is_air_dash_available(fighter: &mut L2CFighterCommon) -> bool {
+        let config = get_air_dash_config(fighter);
+        if !config.enabled
+        || fighter.global_table[SITUATION_KIND].get_i32() != *SITUATION_KIND_AIR
+        || is_air_dash_blocked_by_super_jump(fighter)
+        || WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_AIR_DASH_COUNT) >= config.count_max {
+            return false;
+        }
+        if config.min_height > 0.0 {
+            let pos = *PostureModule::pos(fighter.module_accessor);
+            let height = GroundModule::get_distance_to_floor(fighter.module_accessor, &pos, pos.y, true);
+            if 0.0 <= height && height < config.min_height {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Turns Air Dodge into an air dash when the fighter has an AirDashConfig.
+    /// Call this every frame you're in the air. Only works when the game would let you air dodge.
+    pub unsafe fn air_dash_check(fighter: &mut L2CFighterCommon) -> L2CValue {
+        if !is_air_dash_available(fighter)
+        || !WorkModule::is_enable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ESCAPE_AIR)
+        || fighter.global_table[CMD_CAT1].get_i32() & *FIGHTER_PAD_CMD_CAT1_FLAG_AIR_ESCAPE == 0 {
+            return false.into();
+        }
+        start_air_dash(fighter);
+        true.into()
+    }
+
+    /// Changes into the Fall status with FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH on. Air dashes run on top of Fall
+    /// instead of Air Dodge, so they don't use up your air dodge or come with its intangibility.
+    /// Doesn't check if an air dash is available, so use is_air_dash_available first.
+    pub unsafe fn start_air_dash(fighter: &mut L2CFighterCommon) {
+        WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH);
+        StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_FALL, true);
+    }
+
+    /// Starts an air dash. Call this at the start of the Fall status when FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH is on.
+    /// Picks the direction from the stick, uses up one air dash, and turns off gravity and air drift for the length of the dash.
+    /// Returns false if the stick is held in a direction the fighter can't air dash in.
+    pub unsafe fn air_dash_init(fighter: &mut L2CFighterCommon) -> bool {
+        let config = get_air_dash_config(fighter);
+        let stick_dir = get_command_stick_direction(fighter, true);
+        if let Some((dir_x, dir_y)) = config.direction(stick_dir) {
+            WorkModule::set_float(fighter.module_accessor, dir_x, FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_DIR_X);
+            WorkModule::set_float(fighter.module_accessor, dir_y, FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_DIR_Y);
+            WorkModule::inc_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_AIR_DASH_COUNT);
+            WarkModule::reset_f32(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_FRAME);
+            KineticModule::unable_energy(fighter.module_accessor, *FIGHTER_KINETIC_ENERGY_ID_GRAVITY);
+            KineticModule::unable_energy(fighter.module_accessor, *FIGHTER_KINETIC_ENERGY_ID_CONTROL);
+            return true;
+        }
+        WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH);
+        false
+    }
+
+    /// Runs the air dash. Call this every frame of the Fall status when FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH is on.
+    /// Sets the speed from the fighter's speed curve, and allows aerials after aerial_cancel_frame.
+    /// Returns true once the air dash is over, either from running out of frames, landing or cancelling into an aerial.
+    /// Gravity and air drift come back at that point, so the fighter just keeps falling.
+    pub unsafe fn air_dash_main(fighter: &mut L2CFighterCommon) -> L2CValue {
+        let config = get_air_dash_config(fighter);
+        let frame = WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_FRAME);
+        if frame >= config.duration
+        || fighter.global_table[SITUATION_KIND].get_i32() == *SITUATION_KIND_GROUND
+        || (frame >= config.aerial_cancel_frame
+        && aerial_cancel_common(fighter).get_bool()) {
+            end_air_dash(fighter);
+            return true.into();
+        }
+        let speed = config.speed_at(frame);
+        let dir_x = WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_DIR_X);
+        let dir_y = WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_DIR_Y);
+        macros::SET_SPEED_EX(fighter, dir_x * speed, dir_y * speed, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+        if !fighter.global_table[IN_HITLAG].get_bool() {
+            WorkModule::add_float(fighter.module_accessor, 1.0, FIGHTER_INSTANCE_WORK_ID_FLOAT_AIR_DASH_FRAME);
         }
         false.into()
     }
 
+    /// Ends the air dash early, giving back gravity and air drift.
+    pub unsafe fn end_air_dash(fighter: &mut L2CFighterCommon) {
+        WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH);
+        KineticModule::enable_energy(fighter.module_accessor, *FIGHTER_KINETIC_ENERGY_ID_GRAVITY);
+        KineticModule::enable_energy(fighter.module_accessor, *FIGHTER_KINETIC_ENERGY_ID_CONTROL);
+    }
+
+    /// Gives back all air dashes once you land, and drops an air dash that got interrupted by something other than Fall. Call this once per frame.
+    pub unsafe fn air_dash_count_reset_check(fighter: &mut L2CFighterCommon) {
+        if fighter.global_table[SITUATION_KIND].get_i32() == *SITUATION_KIND_GROUND {
+            WarkModule::reset_i32(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_AIR_DASH_COUNT);
+        }
+        if WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH)
+        && fighter.global_table[STATUS_KIND].get_i32() != *FIGHTER_STATUS_KIND_FALL
+        && StatusModule::status_kind_next(fighter.module_accessor) != *FIGHTER_STATUS_KIND_FALL {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_DASH);
+        }
+    }
+
+    /// Records this frame's cat1 flags into every fighter's input buffer. Without this, cancel_exceptions and chain_cancels
+    /// only accept inputs on the current frame. Each call ages the buffers, so it's only run by the frame owner
+    /// (see WarkModule::is_frame_owner). Call this once per frame for every fighter, in the same place as the other *_main functions.
+    /// Inputs made during hitlag or a super freeze stay buffered until it ends.
+    /// The buffer is cleared whenever a new status begins, so only presses made during the current status are accepted.
+    pub unsafe fn input_buffer_main(fighter: &mut L2CFighterCommon) {
+        if !WarkModule::is_frame_owner(fighter.module_accessor) {
+            return;
+        }
+        for (id, buffer) in INPUT_BUFFERS.iter_mut().enumerate() {
+            let module_accessor = WarkModule::get_module_accessor_from_entry_id(id as i32);
+            if module_accessor.is_null() {
+                continue;
+            }
+            let cat1 = ControlModule::get_command_flag_cat(module_accessor, 0);
+            let in_hitlag = StopModule::is_stop(module_accessor) || SUPER_FREEZE.is_active();
+            let status = StatusModule::status_kind(module_accessor);
+            buffer.update(cat1, in_hitlag, status, StatusModule::is_changing(module_accessor));
+        }
+    }
+
+    /// Sets how many frames an input stays in this fighter's input buffer.
+    pub unsafe fn set_input_buffer_window(fighter: &mut L2CFighterCommon, window: u8) {
+        if let Some(slot) = WarkModule::player_slot(&mut INPUT_BUFFERS, fighter.module_accessor) {
+            slot.window = window;
+        }
+    }
+
+    /// Checks if any of the cat1 flags were pressed within the buffer window.
+    pub unsafe fn is_buffered_cat1(fighter: &mut L2CFighterCommon, cat1_compare: i32) -> bool {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        entry_id < PLAYER_MAX && INPUT_BUFFERS[entry_id].is_buffered(cat1_compare)
+    }
+
+    /// Removes the cat1 flags from the input buffer so they can't be used for another cancel.
+    pub unsafe fn consume_buffered_cat1(fighter: &mut L2CFighterCommon, cat1_compare: i32) {
+        if let Some(slot) = WarkModule::player_slot(&mut INPUT_BUFFERS, fighter.module_accessor) {
+            slot.consume(cat1_compare);
+        }
+    }
+
     /// Enables cancels that would normally be impossible using the existing cancel system.
+    /// Inputs made during hitlag or shortly before the cancel window opens are accepted through the input buffer.
     ///
     /// # Arguments
     ///
@@ -156,7 +496,9 @@ pub mod FGCModule {
         && !AttackModule::is_infliction(fighter.module_accessor, *COLLISION_KIND_MASK_ALL)
         && !fighter.global_table[IN_HITLAG].get_bool()
         && cancel_timer > 0.0) {
-            if (cat1 & cat1_compare) != 0 {
+            if (cat1 & cat1_compare) != 0
+            || is_buffered_cat1(fighter, cat1_compare) {
+                consume_buffered_cat1(fighter, cat1_compare);
                 StatusModule::change_status_request_from_script(fighter.module_accessor, next_status, true);
                 return true.into();
             }
@@ -164,7 +506,7 @@ pub mod FGCModule {
         false.into()
     }
 
-    /// Used for moves that are able to cancel into themselves.
+    /// Used for moves that are able to cancel into themselves. Buffered inputs are accepted the same way as cancel_exceptions.
     /// # Arguments
     ///
     /// * `cat1_compare` - The cat1 flag you wish to check in order to transition to the next status (*FIGHTER_PAD_CMD_CAT1_FLAG_XXXXXX)
@@ -189,8 +531,10 @@ pub mod FGCModule {
         && !fighter.global_table[IN_HITLAG].get_bool()
         && cancel_timer > 0.0) {
             let count = WorkModule::get_int(fighter.module_accessor, counter) + 1;
-            if (cat1 & cat1_compare) != 0
-            && count <= max {
+            if count <= max
+            && ((cat1 & cat1_compare) != 0
+            || is_buffered_cat1(fighter, cat1_compare)) {
+                consume_buffered_cat1(fighter, cat1_compare);
                 fighter.attack_mtrans_pre_process();
                 WorkModule::inc_int(fighter.module_accessor, counter);
                 return 1.into();
@@ -199,7 +543,83 @@ pub mod FGCModule {
         0.into()
     }
 
+    /// Sets the target combos for this fighter's player.
+    pub unsafe fn set_target_combos(fighter: &mut L2CFighterCommon, combos: Vec<TargetCombo>) {
+        if let Some(slot) = WarkModule::player_slot(&mut TARGET_COMBOS, fighter.module_accessor) {
+            slot.combos = combos;
+            slot.clear_history();
+        }
+    }
+
+    /// Ends the current target combo string.
+    pub unsafe fn reset_target_combo(fighter: &mut L2CFighterCommon) {
+        if let Some(slot) = WarkModule::player_slot(&mut TARGET_COMBOS, fighter.module_accessor) {
+            slot.clear_history();
+        }
+    }
+
+    /// A more general version of chain_cancels. Records the current motion into the target combo string,
+    /// and if the string and the input match one of the fighter's target combos, changes into that combo's motion
+    /// without leaving the current status. Call this every frame of any status that can start or continue a target combo.
+    /// # Arguments
+    ///
+    /// * `condition` - When the target combo can be input. A combination of CANCEL_ON_HIT, CANCEL_ON_BLOCK and CANCEL_ON_WHIFF.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // Jab, Jab, then Forward Tilt leads into a unique motion on hit or block.
+    /// FGCModule::set_target_combos(fighter, [
+    ///     TargetCombo {
+    ///         moves: [hash40("attack_11"), hash40("attack_12")].to_vec(),
+    ///         cat1: *FIGHTER_PAD_CMD_CAT1_FLAG_ATTACK_S3,
+    ///         motion: hash40("attack_13_tc")
+    ///     }
+    /// ].to_vec());
+    /// if StatusModule::status_kind(fighter.module_accessor) == *FIGHTER_STATUS_KIND_ATTACK {
+    ///     FGCModule::target_combo_check(fighter, CANCEL_ON_HIT | CANCEL_ON_BLOCK);
+    /// }
+    /// ```
+    pub unsafe fn target_combo_check(fighter: &mut L2CFighterCommon, condition: i32) -> L2CValue {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || TARGET_COMBOS[entry_id].combos.is_empty() {
+            return false.into();
+        }
+        let motion = MotionModule::motion_kind(fighter.module_accessor);
+        TARGET_COMBOS[entry_id].push_motion(motion);
+        if !is_cancel_condition(fighter, condition) {
+            return false.into();
+        }
+        let mut cat1 = fighter.global_table[CMD_CAT1].get_i32();
+        for combo in TARGET_COMBOS[entry_id].combos.iter() {
+            if INPUT_BUFFERS[entry_id].is_buffered(combo.cat1) {
+                cat1 |= combo.cat1;
+            }
+        }
+        let found = TARGET_COMBOS[entry_id].find_match(cat1).map(|combo| (combo.motion, combo.cat1));
+        if let Some((next_motion, combo_cat1)) = found {
+            consume_buffered_cat1(fighter, combo_cat1);
+            MotionModule::change_motion(
+                fighter.module_accessor,
+                Hash40::new_raw(next_motion),
+                0.0,
+                1.0,
+                false,
+                0.0,
+                false,
+                false
+            );
+            AttackModule::clear_all(fighter.module_accessor);
+            WarkModule::reset_f32(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_FLOAT_CANCEL_TIMER);
+            TARGET_COMBOS[entry_id].push_motion(next_motion);
+            return true.into();
+        }
+        false.into()
+    }
+
     /// The generic cancel system, used to enable special cancels for any character.
+    /// All routes are allowed on hit or block. Use cancel_routes if you need whiff cancels or different conditions per route.
     /// # Arguments
     ///
     /// * `normal_cancels` - A vector of all of the ground attack transition terms you can cancel into.
@@ -207,35 +627,142 @@ pub mod FGCModule {
     /// * `aerial_cancel` - Checks if you can cancel into an aerial.
     /// * `jump_cancel` - Checks if you can jump-cancel. 0 = None | 1 = On Hit | 2 = On Hit or Block
     pub unsafe fn cancel_system(fighter: &mut L2CFighterCommon, normal_cancels: Vec<i32>, special_cancels: Vec<i32>, aerial_cancel: bool, jump_cancel: i32) {
-        let cancel_timer = WorkModule::get_float(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_FLOAT_CANCEL_TIMER);
-        if (AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_HIT)
-        || AttackModule::is_infliction_status(fighter.module_accessor, *COLLISION_KIND_MASK_SHIELD))
-        && !AttackModule::is_infliction(fighter.module_accessor, *COLLISION_KIND_MASK_ALL)
-        && !fighter.global_table[IN_HITLAG].get_bool()
-        && cancel_timer > 0.0 {
-            if jump_cancel != 0
-            && jump_cancel_check_hit(fighter, jump_cancel == 2).get_bool() {
-                return;
-            }
-            let sit = fighter.global_table[SITUATION_KIND].get_i32();
-            if special_cancels.is_empty() == false
-            && special_cancel_common(fighter, sit.into(), special_cancels).get_bool() {
-                return;
-            }
-            if aerial_cancel
-            && sit == *SITUATION_KIND_AIR
-            && aerial_cancel_common(fighter).get_bool() {
-                return;
-            }
-            if normal_cancels.is_empty() == false
-            && sit == *SITUATION_KIND_GROUND
-            && normal_cancel_common(fighter, normal_cancels).get_bool() {
-                return;
-            }
+        let on_contact = CANCEL_ON_HIT | CANCEL_ON_BLOCK;
+        let routes = CancelRoutes {
+            jump: match jump_cancel {
+                1 => CANCEL_ON_HIT,
+                2 => on_contact,
+                _ => 0
+            },
+            aerial: if aerial_cancel { on_contact } else { 0 },
+            normals: normal_cancels,
+            normal: on_contact,
+            specials: special_cancels,
+            special: on_contact,
+            ..Default::default()
+        };
+        cancel_routes(fighter, &routes);
+    }
+
+    /// The cancel system with a separate hit/block/whiff condition for every route.
+    /// Cancels are checked in the order jump, dash, air dash, special, aerial, then normal.
+    /// The dash routes accept diagonals and double-taps, and become air dashes in the air (see dash_cancel_check_kind).
+    /// If an aerial gets cancelled without making contact, FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_ATTACK_WHIFF is turned on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // Forward Tilt can be jump-cancelled on hit, and special-cancelled on hit, block, or whiff from frame 8 to frame 20.
+    /// FGCModule::cancel_routes(fighter, &CancelRoutes {
+    ///     jump: CANCEL_ON_HIT,
+    ///     specials: [*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_SPECIAL_N].to_vec(),
+    ///     special: CANCEL_ON_ALL,
+    ///     special_window: CancelWindow::new(8.0, 20.0),
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub unsafe fn cancel_routes(fighter: &mut L2CFighterCommon, routes: &CancelRoutes) -> L2CValue {
+        let contact = get_cancel_contact(fighter);
+        let air_dash_contact = cancel_contact(fighter, true);
+        if contact == 0
+        && air_dash_contact == 0 {
+            return false.into();
+        }
+        let open = routes.open_routes(contact, air_dash_contact, MotionModule::frame(fighter.module_accessor));
+        let sit = fighter.global_table[SITUATION_KIND].get_i32();
+        let mut ret = (open.jump
+        && jump_cancel_check_condition(fighter, contact).get_bool())
+        || ((open.dash || open.back_dash)
+        && dash_cancel_check_kind(fighter, contact, routes.dash_kind, open.dash, open.back_dash).get_bool())
+        || (open.air_dash
+        && air_dash_cancel_check_condition(fighter, air_dash_contact).get_bool());
+        if !ret
+        && open.special {
+            ret = special_cancel_common(fighter, sit.into(), routes.specials.clone()).get_bool();
+        }
+        if !ret
+        && open.aerial
+        && sit == *SITUATION_KIND_AIR {
+            ret = aerial_cancel_common(fighter).get_bool();
+        }
+        if !ret
+        && open.normal
+        && sit == *SITUATION_KIND_GROUND {
+            ret = normal_cancel_common(fighter, routes.normals.clone()).get_bool();
+        }
+        if ret
+        && contact == CANCEL_ON_WHIFF
+        && fighter.global_table[STATUS_KIND].get_i32() == *FIGHTER_STATUS_KIND_ATTACK_AIR {
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_AIR_ATTACK_WHIFF);
+        }
+        ret.into()
+    }
+
+    /// Enables kara cancels, letting a special input during the first few frames of a status cancel into that special.
+    /// Unlike cancel_system, this doesn't need the move to hit anything. Since the special is checked every frame of the window,
+    /// plinking a special a frame after a normal also comes out as the special.
+    /// The speed you had when cancelling is stored so apply_kara_momentum can carry it into the special.
+    /// Any kara cancel left over from an earlier status is cleared first, so its speed can't carry into an unrelated special.
+    /// # Arguments
+    ///
+    /// * `window` - How many frames from the start of the motion the kara cancel is allowed.
+    /// * `special_cancels` - A vector of all of the special move transition terms you can kara cancel into.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // Lets the first 2 frames of a dash be cancelled into Neutral or Side Special.
+    /// if StatusModule::status_kind(fighter.module_accessor) == *FIGHTER_STATUS_KIND_DASH {
+    ///     FGCModule::kara_cancel_check(fighter, 2.0, [
+    ///         *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_SPECIAL_N,
+    ///         *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_SPECIAL_S
+    ///     ].to_vec());
+    /// }
+    /// ```
+    pub unsafe fn kara_cancel_check(fighter: &mut L2CFighterCommon, window: f32, special_cancels: Vec<i32>) -> L2CValue {
+        WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_KARA_CANCEL);
+        if MotionModule::frame(fighter.module_accessor) >= window
+        || fighter.global_table[IN_HITLAG].get_bool() {
+            return false.into();
+        }
+        let lr = PostureModule::lr(fighter.module_accessor);
+        let speed_x = KineticModule::get_sum_speed_x(fighter.module_accessor, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN) * lr;
+        let speed_y = KineticModule::get_sum_speed_y(fighter.module_accessor, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+        let sit = fighter.global_table[SITUATION_KIND].get_i32();
+        if special_cancel_common(fighter, sit.into(), special_cancels).get_bool() {
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_KARA_CANCEL);
+            WorkModule::set_float(fighter.module_accessor, speed_x, FIGHTER_INSTANCE_WORK_ID_FLOAT_KARA_SPEED_X);
+            WorkModule::set_float(fighter.module_accessor, speed_y, FIGHTER_INSTANCE_WORK_ID_FLOAT_KARA_SPEED_Y);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Carries the speed from a kara cancel into the special. Call this at the start of any special you can kara cancel into.
+    /// Does nothing if the special wasn't kara cancelled into.
+    pub unsafe fn apply_kara_momentum(fighter: &mut L2CFighterCommon) {
+        if WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_KARA_CANCEL) {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_KARA_CANCEL);
+            let speed_x = WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_KARA_SPEED_X);
+            let speed_y = WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_KARA_SPEED_Y);
+            macros::SET_SPEED_EX(fighter, speed_x, speed_y, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+        }
+    }
+
+    /// Sets the stick settings used by get_command_stick_direction for this fighter's player.
+    pub unsafe fn set_stick_config(fighter: &mut L2CFighterCommon, config: StickConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut STICK_CONFIGS, fighter.module_accessor) {
+            *slot = config;
         }
     }
 
+    /// Gets the stick settings for this fighter's player.
+    pub unsafe fn get_stick_config(fighter: &mut L2CFighterCommon) -> StickConfig {
+        WarkModule::player_slot(&mut STICK_CONFIGS, fighter.module_accessor).copied().unwrap_or(StickConfig::DEFAULT)
+    }
+
     /// Checks the direction of the left stick and returns a number between 1 and 9, representing numpad notation.
+    /// The deadzone and angle bands come from the player's StickConfig (see set_stick_config).
     /// # Arguments
     /// 
     /// * `command` - Set to true to have the horizontal stick value reversed, so that it checks the input as if you are facing right.
@@ -249,39 +776,26 @@ pub mod FGCModule {
                 stick_x *= -1.0;
             }
         }
-        let length = sv_math::vec2_length(stick_x, stick_y);
-        if length < 0.25 {
+        let config = get_stick_config(fighter);
+        direction_from_stick(stick_x, stick_y, &config)
+    }
+
+    /// Resolves digital directions (such as from a leverless controller) into numpad notation,
+    /// using the player's SOCD setting. Call this once per frame.
+    /// # Arguments
+    ///
+    /// * `command` - Set to true to have left and right swapped when facing left, so that it checks the input as if you are facing right.
+    pub unsafe fn get_digital_stick_direction(fighter: &mut L2CFighterCommon, left: bool, right: bool, down: bool, up: bool, command: bool) -> i32 {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
             return 5;
         }
-        let normalize = sv_math::vec2_normalize(stick_x, stick_y);
-        let dirx = normalize.x;
-        let diry = normalize.y;
-        let arctangent = diry.atan2(dirx.abs());
-        let degrees = arctangent.to_degrees();
-        if degrees.abs() <= 15.0 {
-            if stick_x > 0.0 {
-                return 6;
-            }
-            return 4;
-        }
-        else if 70.0 <= degrees.abs() {
-            if stick_y > 0.0 {
-                return 8;
-            }
-            return 2;
-        }
-        else {
-            if stick_x > 0.0 {
-                if stick_y > 0.0 {
-                    return 9;
-                }
-                return 3;
-            }
-            if stick_y > 0.0 {
-                return 7;
-            }
-            return 1;
+        let config = STICK_CONFIGS[entry_id];
+        let (mut x, y) = SOCD_STATES[entry_id].resolve(left, right, down, up, config.socd);
+        if command && PostureModule::lr(fighter.module_accessor) < 0.0 {
+            x *= -1;
         }
+        direction_from_axes(x, y, config.mode)
     }
 
     /// Used for command inputs. Currently goes unused.
@@ -304,52 +818,107 @@ pub mod FGCModule {
     // }
 
     /// Disables a grounded attack. Used for cancel systems with complex cancel trees.
+    /// This also records the attack as the latest one in the current string for the fighter's ground gatling table.
     pub unsafe fn disable_ground_normal(fighter: &mut L2CFighterCommon, ground_normal_mask: i32) {
         if !CancelModule::is_enable_cancel(fighter.module_accessor) {
-            let mut used_ground_normals = WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_USED_GROUND_NORMALS);
-            if used_ground_normals & ground_normal_mask == 0 {
-                used_ground_normals += ground_normal_mask;
-            }
-            WorkModule::set_int(fighter.module_accessor, used_ground_normals, FIGHTER_INSTANCE_WORK_ID_INT_USED_GROUND_NORMALS);
+            let mut string = get_ground_normal_string(fighter);
+            string.record(ground_normal_mask);
+            set_ground_normal_string(fighter, string);
+        }
+    }
+
+    /// Gets the fighter's current grounded normal string.
+    pub unsafe fn get_ground_normal_string(fighter: &mut L2CFighterCommon) -> GatlingString {
+        GatlingString {
+            last: WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_LAST_GROUND_NORMAL),
+            used: WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_USED_GROUND_NORMALS),
+            length: WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_GROUND_NORMAL_STRING_LENGTH)
+        }
+    }
+
+    unsafe fn set_ground_normal_string(fighter: &mut L2CFighterCommon, string: GatlingString) {
+        WorkModule::set_int(fighter.module_accessor, string.last, FIGHTER_INSTANCE_WORK_ID_INT_LAST_GROUND_NORMAL);
+        WorkModule::set_int(fighter.module_accessor, string.used, FIGHTER_INSTANCE_WORK_ID_INT_USED_GROUND_NORMALS);
+        WorkModule::set_int(fighter.module_accessor, string.length, FIGHTER_INSTANCE_WORK_ID_INT_GROUND_NORMAL_STRING_LENGTH);
+    }
+
+    /// Returns the ATTACK_XX_MASK for a grounded attack status, including the charge and hold statuses of smash attacks.
+    /// Returns 0 for anything that isn't a grounded normal.
+    pub unsafe fn get_ground_normal_mask(status_kind: i32) -> i32 {
+        let kinds : [&[i32]; 8] = [
+            &[*FIGHTER_STATUS_KIND_ATTACK, *FIGHTER_STATUS_KIND_ATTACK_100],
+            &[*FIGHTER_STATUS_KIND_ATTACK_S3],
+            &[*FIGHTER_STATUS_KIND_ATTACK_HI3],
+            &[*FIGHTER_STATUS_KIND_ATTACK_LW3],
+            &[*FIGHTER_STATUS_KIND_ATTACK_S4_START, *FIGHTER_STATUS_KIND_ATTACK_S4_HOLD, *FIGHTER_STATUS_KIND_ATTACK_S4],
+            &[*FIGHTER_STATUS_KIND_ATTACK_HI4_START, *FIGHTER_STATUS_KIND_ATTACK_HI4_HOLD, *FIGHTER_STATUS_KIND_ATTACK_HI4],
+            &[*FIGHTER_STATUS_KIND_ATTACK_LW4_START, *FIGHTER_STATUS_KIND_ATTACK_LW4_HOLD, *FIGHTER_STATUS_KIND_ATTACK_LW4],
+            &[*FIGHTER_STATUS_KIND_ATTACK_DASH]
+        ];
+        mask_for_kind(status_kind, &kinds)
+    }
+
+    /// Returns every transition term that leads into the grounded normals in the mask.
+    pub unsafe fn get_ground_normal_transition_terms(ground_normal_mask: i32) -> Vec<i32> {
+        let mut terms = Vec::new();
+        if ground_normal_mask & ATTACK_N_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK);
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SWING);
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SHOOT);
+        }
+        if ground_normal_mask & ATTACK_S3_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_S3);
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SWING_3);
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SHOOT_S3);
+        }
+        if ground_normal_mask & ATTACK_HI3_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_HI3);
+        }
+        if ground_normal_mask & ATTACK_LW3_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_LW3);
+        }
+        if ground_normal_mask & ATTACK_S4_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_S4_START);
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SWING_4);
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SHOOT_S4);
+        }
+        if ground_normal_mask & ATTACK_HI4_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_HI4_START);
+        }
+        if ground_normal_mask & ATTACK_LW4_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_LW4_START);
+        }
+        if ground_normal_mask & ATTACK_DASH_MASK != 0 {
+            terms.push(*FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_DASH);
         }
+        terms
+    }
+
+    /// Sets the ground gatling table for this fighter's player. Pass None to go back to only blocking normals that were already used.
+    pub unsafe fn set_ground_gatling(fighter: &mut L2CFighterCommon, gatling: Option<GroundGatling>) {
+        if let Some(slot) = WarkModule::player_slot(&mut GROUND_GATLINGS, fighter.module_accessor) {
+            *slot = gatling;
+        }
+    }
+
+    /// Gets the ground gatling table for this fighter's player, if they have one.
+    pub unsafe fn get_ground_gatling(fighter: &mut L2CFighterCommon) -> Option<GroundGatling> {
+        WarkModule::player_slot(&mut GROUND_GATLINGS, fighter.module_accessor).copied().flatten()
     }
 
     /// Used when checking for attack inputs to disable certain attacks if they are used in a string.
+    /// If the fighter has a ground gatling table, every normal that isn't a follow-up of the current one is disabled instead.
     /// Used for cancel systems with complex cancel trees.
     pub unsafe fn set_used_ground_normal_transition_terms(fighter: &mut L2CFighterCommon) {
         if !CancelModule::is_enable_cancel(fighter.module_accessor) {
-            let used_mask = WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_USED_GROUND_NORMALS);
-            if used_mask & ATTACK_N_MASK != 0 {
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK);
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SWING);
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SHOOT);
-            }
-            if used_mask & ATTACK_S3_MASK != 0 {
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_S3);
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SWING_3);
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SHOOT_S3);
-            }
-            if used_mask & ATTACK_HI3_MASK != 0 {
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_HI3);
-            }
-            if used_mask & ATTACK_LW3_MASK != 0 {
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_LW3);
-            }
-            if used_mask & ATTACK_S4_MASK != 0 {
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_S4_START);
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SWING_4);
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ITEM_SHOOT_S4);
-            }
-            if used_mask & ATTACK_HI4_MASK != 0 {
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_HI4_START);
-            }
-            if used_mask & ATTACK_LW4_MASK != 0 {
-                WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_LW4_START);
+            let disabled_mask = disabled_ground_normals(get_ground_gatling(fighter), get_ground_normal_string(fighter));
+            for term in get_ground_normal_transition_terms(disabled_mask).iter() {
+                WorkModule::unable_transition_term(fighter.module_accessor, *term);
             }
         }
     }
 
-    /// Resets your current cancel string. Typically only resets if CancelModule::is_enable_cancel is true, but
+    /// Resets your current cancel string, including the target combo string. Typically only resets if CancelModule::is_enable_cancel is true, but
     /// the ignore flag can be passed to bypass that.
     pub unsafe fn reset_used_ground_normals(fighter: &mut L2CFighterCommon, ignore: bool) {
         if ignore || (CancelModule::is_enable_cancel(fighter.module_accessor)
@@ -371,52 +940,1164 @@ pub mod FGCModule {
             *FIGHTER_STATUS_KIND_ATTACK_LW4_HOLD,
             *FIGHTER_STATUS_KIND_ATTACK_LW4
         ].contains(&fighter.global_table[STATUS_KIND].get_i32()) {
-            WorkModule::set_int(fighter.module_accessor, 0, FIGHTER_INSTANCE_WORK_ID_INT_USED_GROUND_NORMALS);
+            set_ground_normal_string(fighter, GatlingString::NEW);
+            reset_target_combo(fighter);
         }
     }
 
     /// Disables an aerial. Used for characters who can cancel aerials into other aerials.
+    /// This also records the aerial as the latest one in the current string for the fighter's aerial gatling table.
     pub unsafe fn disable_aerial(fighter: &mut L2CFighterCommon, aerial_mask: i32) {
         if !CancelModule::is_enable_cancel(fighter.module_accessor) {
-            let mut used_aerials = WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS);
-            if used_aerials & aerial_mask == 0 {
-                used_aerials += aerial_mask;
-            }
-            WorkModule::set_int(fighter.module_accessor, used_aerials, FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS);
+            let mut string = get_aerial_string(fighter);
+            string.record(aerial_mask);
+            set_aerial_string(fighter, string);
+        }
+    }
+
+    /// Gets the fighter's current aerial string.
+    pub unsafe fn get_aerial_string(fighter: &mut L2CFighterCommon) -> GatlingString {
+        GatlingString {
+            last: WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_LAST_AERIAL),
+            used: WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS),
+            length: WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_AERIAL_STRING_LENGTH)
+        }
+    }
+
+    unsafe fn set_aerial_string(fighter: &mut L2CFighterCommon, string: GatlingString) {
+        WorkModule::set_int(fighter.module_accessor, string.last, FIGHTER_INSTANCE_WORK_ID_INT_LAST_AERIAL);
+        WorkModule::set_int(fighter.module_accessor, string.used, FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS);
+        WorkModule::set_int(fighter.module_accessor, string.length, FIGHTER_INSTANCE_WORK_ID_INT_AERIAL_STRING_LENGTH);
+    }
+
+    /// Converts the result of ControlModule::get_attack_air_kind into an ATTACK_AIR_XX_MASK. Returns 0 for anything that isn't an aerial.
+    pub unsafe fn get_aerial_mask(attack_air_kind: i32) -> i32 {
+        let kinds : [&[i32]; 5] = [
+            &[*FIGHTER_COMMAND_ATTACK_AIR_KIND_N],
+            &[*FIGHTER_COMMAND_ATTACK_AIR_KIND_F],
+            &[*FIGHTER_COMMAND_ATTACK_AIR_KIND_B],
+            &[*FIGHTER_COMMAND_ATTACK_AIR_KIND_HI],
+            &[*FIGHTER_COMMAND_ATTACK_AIR_KIND_LW]
+        ];
+        mask_for_kind(attack_air_kind, &kinds)
+    }
+
+    /// Sets the aerial gatling table for this fighter's player. Pass None to go back to using FIGHTER_STATUS_WORK_ID_INT_ENABLED_AERIALS.
+    pub unsafe fn set_aerial_gatling(fighter: &mut L2CFighterCommon, gatling: Option<AerialGatling>) {
+        if let Some(slot) = WarkModule::player_slot(&mut AERIAL_GATLINGS, fighter.module_accessor) {
+            *slot = gatling;
         }
     }
 
+    /// Gets the aerial gatling table for this fighter's player, if they have one.
+    pub unsafe fn get_aerial_gatling(fighter: &mut L2CFighterCommon) -> Option<AerialGatling> {
+        WarkModule::player_slot(&mut AERIAL_GATLINGS, fighter.module_accessor).copied().flatten()
+    }
+
     /// Checks if certain aerials are enabled.
+    /// If the fighter has an aerial gatling table, that table decides which aerials can follow the current one.
+    /// Otherwise, FIGHTER_STATUS_WORK_ID_INT_ENABLED_AERIALS is used.
     pub unsafe fn check_enabled_aerial(fighter: &mut L2CFighterCommon) -> bool {
         if WorkModule::is_flag(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_FLAG_NORMAL_CANCEL)
         && !CancelModule::is_enable_cancel(fighter.module_accessor) {
-            let enabled_mask = WorkModule::get_int(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_INT_ENABLED_AERIALS);
-            let used_mask = WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS);
+            let string = get_aerial_string(fighter);
             let attack_air_kind = ControlModule::get_attack_air_kind(fighter.module_accessor);
-            let aerial_flag;
-            match attack_air_kind {
-                1 => aerial_flag = ATTACK_AIR_N_MASK,
-                2 => aerial_flag = ATTACK_AIR_F_MASK,
-                3 => aerial_flag = ATTACK_AIR_B_MASK,
-                4 => aerial_flag = ATTACK_AIR_HI_MASK,
-                5 => aerial_flag = ATTACK_AIR_LW_MASK,
-                _ => aerial_flag = 0b00000
+            let aerial_flag = get_aerial_mask(attack_air_kind);
+            if aerial_flag == 0 {
+                return false;
+            }
+            if let Some(gatling) = get_aerial_gatling(fighter) {
+                return gatling.is_allowed(string.last, string.used, string.length, aerial_flag);
             }
-            return enabled_mask & aerial_flag != 0 && used_mask & aerial_flag == 0;
+            let enabled_mask = WorkModule::get_int(fighter.module_accessor, FIGHTER_STATUS_WORK_ID_INT_ENABLED_AERIALS);
+            return enabled_mask & aerial_flag != 0 && string.used & aerial_flag == 0;
         }
         true
     }
 
     /// Resets your aerial cancel string, enabling all aerials again.
     pub unsafe fn reset_used_aerials(fighter: &mut L2CFighterCommon) {
-        WorkModule::set_int(fighter.module_accessor, 0, FIGHTER_INSTANCE_WORK_ID_INT_USED_AERIALS);
+        set_aerial_string(fighter, GatlingString::NEW);
     }
 
-    /// Handles adding or subtracting meter.
-    pub unsafe fn update_meter(object: *mut BattleObject, amount: f32, meter_max: f32, meter_const: i32) {
-        let module_accessor = (*object).module_accessor;
-        let mut meter = WorkModule::get_float(module_accessor, meter_const);
-        meter += amount;
+    /// Starts a new aerial string after a jump-cancel, if the fighter's aerial gatling table allows it.
+    unsafe fn reset_aerial_string_on_jump(fighter: &mut L2CFighterCommon) {
+        if let Some(gatling) = get_aerial_gatling(fighter) {
+            let string = gatling.string_after_jump(get_aerial_string(fighter));
+            set_aerial_string(fighter, string);
+        }
+    }
+
+    /// Sets the super jump settings for this fighter's player.
+    pub unsafe fn set_super_jump_config(fighter: &mut L2CFighterCommon, config: SuperJumpConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut SUPER_JUMP_CONFIGS, fighter.module_accessor) {
+            *slot = config;
+        }
+    }
+
+    /// Gets the super jump settings for this fighter's player.
+    pub unsafe fn get_super_jump_config(fighter: &mut L2CFighterCommon) -> SuperJumpConfig {
+        WarkModule::player_slot(&mut SUPER_JUMP_CONFIGS, fighter.module_accessor).copied().unwrap_or(SuperJumpConfig::DISABLED)
+    }
+
+    /// Tracks the down half of the 2 -> 8 super jump input. Holding down refreshes FIGHTER_INSTANCE_WORK_ID_FLOAT_SUPER_JUMP_FRAME,
+    /// which then counts down once you let go. Call this once per frame.
+    pub unsafe fn super_jump_input_check(fighter: &mut L2CFighterCommon) {
+        let config = get_super_jump_config(fighter);
+        if !config.enabled {
+            return;
+        }
+        if is_down_direction(get_command_stick_direction(fighter, false)) {
+            WorkModule::set_float(fighter.module_accessor, config.window, FIGHTER_INSTANCE_WORK_ID_FLOAT_SUPER_JUMP_FRAME);
+        }
+        else if WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_SUPER_JUMP_FRAME) > 0.0 {
+            WarkModule::count_down(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_SUPER_JUMP_FRAME, 1.0);
+        }
+    }
+
+    /// Checks if the jump you're about to do is a super jump, turning on FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP if so.
+    /// Call this during Jump Squat.
+    pub unsafe fn super_jump_check(fighter: &mut L2CFighterCommon) -> bool {
+        let config = get_super_jump_config(fighter);
+        if config.enabled
+        && WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_SUPER_JUMP_FRAME) > 0.0
+        && is_up_direction(get_command_stick_direction(fighter, false)) {
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP);
+            WarkModule::reset_f32(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_SUPER_JUMP_FRAME);
+            return true;
+        }
+        false
+    }
+
+    /// Scales the jump's speed if it's a super jump. Call this once at the start of the Jump status.
+    pub unsafe fn apply_super_jump(fighter: &mut L2CFighterCommon) {
+        if !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP) {
+            return;
+        }
+        let config = get_super_jump_config(fighter);
+        let mul = Vector3f { x: config.speed_x_mul, y: config.speed_y_mul(), z: 1.0 };
+        KineticModule::mul_speed(fighter.module_accessor, &mul, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+    }
+
+    /// Applies the restrictions on double jumps and air dashes after a super jump, and ends the super jump once you land.
+    /// Call this once per frame.
+    pub unsafe fn super_jump_restrictions(fighter: &mut L2CFighterCommon) {
+        if !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP) {
+            return;
+        }
+        // A super-jump-cancel turns the flag on while still grounded, before Jump Squat starts.
+        if fighter.global_table[SITUATION_KIND].get_i32() == *SITUATION_KIND_GROUND
+        && fighter.global_table[STATUS_KIND].get_i32() != *FIGHTER_STATUS_KIND_JUMP_SQUAT
+        && StatusModule::status_kind_next(fighter.module_accessor) != *FIGHTER_STATUS_KIND_JUMP_SQUAT {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP);
+            return;
+        }
+        let config = get_super_jump_config(fighter);
+        if !config.allow_jump_aerial {
+            let jump_count_max = WorkModule::get_int(fighter.module_accessor, *FIGHTER_INSTANCE_WORK_ID_INT_JUMP_COUNT_MAX);
+            WorkModule::set_int(fighter.module_accessor, jump_count_max, *FIGHTER_INSTANCE_WORK_ID_INT_JUMP_COUNT);
+        }
+        if !config.allow_air_dash {
+            WorkModule::unable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ESCAPE_AIR);
+        }
+    }
+
+    /// Checks if a super jump is stopping you from air dashing or air dodging (see SuperJumpConfig::allow_air_dash).
+    /// The cancel functions turn the air dodge transition term back on, so they check this instead of relying on super_jump_restrictions.
+    pub unsafe fn is_air_dash_blocked_by_super_jump(fighter: &mut L2CFighterCommon) -> bool {
+        WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP)
+        && !get_super_jump_config(fighter).allow_air_dash
+    }
+
+    /// Enables super-jump-cancels. Holding up when the condition (CANCEL_ON_HIT, CANCEL_ON_BLOCK, CANCEL_ON_WHIFF) is met
+    /// jump-cancels into a super jump, like a launcher.
+    pub unsafe fn super_jump_cancel_check(fighter: &mut L2CFighterCommon, condition: i32) -> L2CValue {
+        if !get_super_jump_config(fighter).enabled
+        || !is_up_direction(get_command_stick_direction(fighter, false)) {
+            return false.into();
+        }
+        WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP);
+        if jump_cancel_check_condition(fighter, condition).get_bool() {
+            return true.into();
+        }
+        WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_SUPER_JUMP);
+        false.into()
+    }
+
+    /// Sets the wall settings (wall jumps, wall clings and wall runs) for this fighter's player.
+    pub unsafe fn set_wall_config(fighter: &mut L2CFighterCommon, config: WallConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut WALL_CONFIGS, fighter.module_accessor) {
+            *slot = config;
+        }
+    }
+
+    /// Gets the wall settings for this fighter's player.
+    pub unsafe fn get_wall_config(fighter: &mut L2CFighterCommon) -> WallConfig {
+        WarkModule::player_slot(&mut WALL_CONFIGS, fighter.module_accessor).copied().unwrap_or(WallConfig::DEFAULT)
+    }
+
+    /// Returns which side of the fighter a wall is touching. 1.0 for the right, -1.0 for the left, or 0.0 for no wall.
+    pub unsafe fn get_touched_wall_side(fighter: &mut L2CFighterCommon) -> f32 {
+        if GroundModule::is_wall_touch_line(fighter.module_accessor, *GROUND_TOUCH_FLAG_RIGHT_SIDE as u32) {
+            1.0
+        }
+        else if GroundModule::is_wall_touch_line(fighter.module_accessor, *GROUND_TOUCH_FLAG_LEFT_SIDE as u32) {
+            -1.0
+        }
+        else {
+            0.0
+        }
+    }
+
+    /// Forces a wall jump when touching a wall, using the input set in the fighter's WallConfig.
+    /// Wall jumps are limited to wall_jump_max per airtime.
+    pub unsafe fn wall_jump_check(fighter: &mut L2CFighterCommon) -> L2CValue {
+        let config = get_wall_config(fighter);
+        let count = WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_WALL_JUMP_COUNT);
+        let wall_side = get_touched_wall_side(fighter);
+        if wall_side == 0.0
+        || !config.can_wall_jump(count) {
+            return false.into();
+        }
+        let cat1 = fighter.global_table[CMD_CAT1].get_i32();
+        let lr = PostureModule::lr(fighter.module_accessor);
+        let away = if (wall_side > 0.0) == (lr < 0.0) { *FIGHTER_PAD_CMD_CAT1_FLAG_DASH } else { *FIGHTER_PAD_CMD_CAT1_FLAG_TURN_DASH };
+        let away_from_wall = cat1 & away != 0;
+        let jump = cat1 & *FIGHTER_PAD_CMD_CAT1_FLAG_JUMP_BUTTON != 0;
+        if config.input.is_input(away_from_wall, jump) {
+            WorkModule::inc_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_WALL_JUMP_COUNT);
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_CLING);
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_RUN);
+            StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_WALL_JUMP, true);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Lets the fighter cling to a wall by holding towards it, limiting their fall speed to cling_slide_speed.
+    /// Turns FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_CLING on while clinging.
+    pub unsafe fn wall_cling_check(fighter: &mut L2CFighterCommon) {
+        let config = get_wall_config(fighter);
+        let wall_side = get_touched_wall_side(fighter);
+        let stick_x = fighter.global_table[STICK_X].get_f32();
+        if !config.enabled
+        || !config.cling
+        || wall_side == 0.0
+        || stick_x * wall_side < 0.5
+        || fighter.global_table[SITUATION_KIND].get_i32() != *SITUATION_KIND_AIR {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_CLING);
+            return;
+        }
+        WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_CLING);
+        let speed_y = KineticModule::get_sum_speed_y(fighter.module_accessor, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+        if speed_y < -config.cling_slide_speed {
+            macros::SET_SPEED_EX(fighter, 0.0, -config.cling_slide_speed, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+        }
+    }
+
+    /// Lets the fighter run up a wall by holding up towards it, moving them up at wall_run_speed for up to wall_run_frames per airtime.
+    /// Turns FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_RUN on while wall running. Returns true on the frames you're wall running.
+    pub unsafe fn wall_run_check(fighter: &mut L2CFighterCommon) -> bool {
+        let config = get_wall_config(fighter);
+        let frames_used = WorkModule::get_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_WALL_RUN_FRAME);
+        let wall_side = get_touched_wall_side(fighter);
+        let stick_x = fighter.global_table[STICK_X].get_f32();
+        let stick_y = fighter.global_table[STICK_Y].get_f32();
+        if !config.can_wall_run(frames_used)
+        || wall_side == 0.0
+        || stick_x * wall_side <= 0.0
+        || stick_y < 0.5
+        || fighter.global_table[SITUATION_KIND].get_i32() != *SITUATION_KIND_AIR {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_RUN);
+            return false;
+        }
+        WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_RUN);
+        WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_CLING);
+        WorkModule::inc_int(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_WALL_RUN_FRAME);
+        macros::SET_SPEED_EX(fighter, 0.0, config.wall_run_speed, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+        true
+    }
+
+    /// In Fighting Game Mode, makes fighters that are launched into a wall bounce off of it.
+    /// Call this every frame for the fighter being launched.
+    pub unsafe fn wall_bounce_check(fighter: &mut L2CFighterCommon) -> L2CValue {
+        if !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_IS_FGC)
+        || ![
+            *FIGHTER_STATUS_KIND_DAMAGE_FLY,
+            *FIGHTER_STATUS_KIND_DAMAGE_FLY_ROLL,
+            *FIGHTER_STATUS_KIND_DAMAGE_FLY_METEOR
+        ].contains(&fighter.global_table[STATUS_KIND].get_i32()) {
+            return false.into();
+        }
+        let wall_side = get_touched_wall_side(fighter);
+        let speed_x = KineticModule::get_sum_speed_x(fighter.module_accessor, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+        if wall_side != 0.0
+        && speed_x * wall_side > 0.0 {
+            StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_DAMAGE_FLY_REFLECT_LR, false);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Runs wall runs, wall clings and wall jumps, and gives wall jumps and wall running back on landing. Call this once per frame.
+    pub unsafe fn wall_mechanics(fighter: &mut L2CFighterCommon) {
+        if fighter.global_table[SITUATION_KIND].get_i32() == *SITUATION_KIND_GROUND {
+            WarkModule::reset_i32(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_WALL_JUMP_COUNT);
+            WarkModule::reset_i32(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_INT_WALL_RUN_FRAME);
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_CLING);
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_WALL_RUN);
+            return;
+        }
+        if !wall_run_check(fighter) {
+            wall_cling_check(fighter);
+        }
+        wall_jump_check(fighter);
+    }
+
+    /// Sets the guard settings (guard-off attack cancels and alpha counters) for this fighter's player.
+    pub unsafe fn set_guard_config(fighter: &mut L2CFighterCommon, config: GuardConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut GUARD_CONFIGS, fighter.module_accessor) {
+            *slot = config;
+        }
+    }
+
+    /// Gets the guard settings for this fighter's player.
+    pub unsafe fn get_guard_config(fighter: &mut L2CFighterCommon) -> GuardConfig {
+        WarkModule::player_slot(&mut GUARD_CONFIGS, fighter.module_accessor).copied().unwrap_or(GuardConfig::DISABLED)
+    }
+
+    /// Opens the attack-cancel window for dropping shield. Call this when the Guard Off status starts.
+    pub unsafe fn guard_off_attack_cancel_init(fighter: &mut L2CFighterCommon) {
+        let config = get_guard_config(fighter);
+        if config.guard_off_cancel {
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_GUARD_OFF_ATTACK_CANCEL);
+            WorkModule::set_int(fighter.module_accessor, config.guard_off_cancel_frame, FIGHTER_STATUS_GUARD_OFF_WORK_INT_ATTACK_CANCEL_FRAME);
+        }
+        else {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_GUARD_OFF_ATTACK_CANCEL);
+        }
+    }
+
+    /// Checks for attacks during the Guard Off attack-cancel window. Call this every frame of the Guard Off status.
+    /// The window closes once FIGHTER_STATUS_GUARD_OFF_WORK_INT_ATTACK_CANCEL_FRAME runs out, and doesn't count down during hitlag.
+    pub unsafe fn guard_off_attack_cancel_check(fighter: &mut L2CFighterCommon) -> L2CValue {
+        if !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_GUARD_OFF_ATTACK_CANCEL) {
+            return false.into();
+        }
+        let frames_left = WorkModule::get_int(fighter.module_accessor, FIGHTER_STATUS_GUARD_OFF_WORK_INT_ATTACK_CANCEL_FRAME);
+        match guard_off_window_step(frames_left, fighter.global_table[IN_HITLAG].get_bool()) {
+            Some(frames_left) => {
+                WorkModule::set_int(fighter.module_accessor, frames_left, FIGHTER_STATUS_GUARD_OFF_WORK_INT_ATTACK_CANCEL_FRAME);
+            },
+            None => {
+                WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_GUARD_OFF_ATTACK_CANCEL);
+                return false.into();
+            }
+        }
+        let sit = fighter.global_table[SITUATION_KIND].get_i32();
+        let specials = [
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_SPECIAL_N,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_SPECIAL_S,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_SPECIAL_HI,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_SPECIAL_LW
+        ].to_vec();
+        let normals = [
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_S3,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_HI3,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_LW3,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_S4_START,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_HI4_START,
+            *FIGHTER_STATUS_TRANSITION_TERM_ID_CONT_ATTACK_LW4_START
+        ].to_vec();
+        if special_cancel_common(fighter, sit.into(), specials).get_bool()
+        || normal_cancel_common(fighter, normals).get_bool() {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_GUARD_OFF_ATTACK_CANCEL);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Spends meter to cancel shieldstun into the fighter's counter move (an "alpha counter").
+    /// Call this every frame of the Guard Damage status. Does nothing unless the fighter's GuardConfig enables it.
+    pub unsafe fn alpha_counter_check(fighter: &mut L2CFighterCommon) -> L2CValue {
+        let config = get_guard_config(fighter);
+        if !config.alpha_counter
+        || fighter.global_table[IN_HITLAG].get_bool()
+        || fighter.global_table[STATUS_KIND].get_i32() != *FIGHTER_STATUS_KIND_GUARD_DAMAGE {
+            return false.into();
+        }
+        let meter = WorkModule::get_float(fighter.module_accessor, config.meter_const);
+        if meter >= config.alpha_counter_cost
+        && fighter.global_table[CMD_CAT1].get_i32() & config.alpha_counter_cat1 != 0 {
+            update_meter(fighter.battle_object, -config.alpha_counter_cost, config.meter_max, config.meter_const);
+            StatusModule::change_status_request_from_script(fighter.module_accessor, config.alpha_counter_status, true);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Sets how this fighter's super freeze looks.
+    pub unsafe fn set_super_freeze_config(fighter: &mut L2CFighterCommon, config: SuperFreezeConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut SUPER_FREEZE_CONFIGS, fighter.module_accessor) {
+            *slot = config;
+        }
+    }
+
+    /// Gets the super freeze settings for this fighter's player.
+    pub unsafe fn get_super_freeze_config(fighter: &mut L2CFighterCommon) -> SuperFreezeConfig {
+        WarkModule::player_slot(&mut SUPER_FREEZE_CONFIGS, fighter.module_accessor).copied().unwrap_or(SuperFreezeConfig::DEFAULT)
+    }
+
+    /// Freezes every fighter while the camera zooms in on this fighter and their flash effect plays, as set in the SuperFreezeConfig.
+    /// The zoom is skipped when there are more than two players, or while the game's own cut-in camera is playing.
+    /// Inputs made during the freeze go into the input buffer, so they come out once it ends.
+    /// Returns false if another super freeze is already happening.
+    pub unsafe fn start_super_freeze(fighter: &mut L2CFighterCommon, config: SuperFreezeConfig) -> bool {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor) as i32;
+        if !SUPER_FREEZE.start(entry_id, config.frames) {
+            return false;
+        }
+        for id in 0..PLAYER_MAX {
+            let module_accessor = WarkModule::get_module_accessor_from_entry_id(id as i32);
+            if !module_accessor.is_null() {
+                StopModule::set_hit_stop_frame(module_accessor, config.frames, false);
+            }
+        }
+        if config.flash_effect != 0 {
+            macros::EFFECT(fighter, Hash40::new_raw(config.flash_effect), Hash40::new("top"), 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, false);
+        }
+        if FighterUtil::get_opponent_fighter_num(fighter.module_accessor, true) < 2
+        && !smash::app::lua_bind::FighterCutInManager::is_play_motion_camera(singletons::FighterCutInManager()) {
+            macros::CAM_ZOOM_IN_arg5(fighter, config.frames as f32, 0.0, config.zoom, 0.0, 0.0);
+            SUPER_FREEZE.set_zoomed();
+        }
+        true
+    }
+
+    /// Spends meter on a super move and starts this fighter's super freeze. Returns false without spending anything
+    /// if there isn't enough meter or another super freeze is already happening.
+    pub unsafe fn spend_meter_super(fighter: &mut L2CFighterCommon, cost: f32, meter_max: f32, meter_const: i32) -> bool {
+        if WorkModule::get_float(fighter.module_accessor, meter_const) < cost
+        || SUPER_FREEZE.is_active() {
+            return false;
+        }
+        update_meter(fighter.battle_object, -cost, meter_max, meter_const);
+        let config = get_super_freeze_config(fighter);
+        start_super_freeze(fighter, config)
+    }
+
+    /// Checks if a super freeze is happening.
+    pub unsafe fn is_super_freeze() -> bool {
+        SUPER_FREEZE.is_active()
+    }
+
+    /// Counts down the super freeze, and returns the camera to normal once it ends.
+    /// If the fighter who started it leaves or gets KO'd, the freeze ends early and everyone is unfrozen.
+    /// Call this once per frame for every fighter. Only the fighter with the lowest entry ID actually runs it,
+    /// so the freeze counts down once per frame no matter who started it.
+    pub unsafe fn super_freeze_main(fighter: &mut L2CFighterCommon) {
+        if !SUPER_FREEZE.is_active()
+        || !WarkModule::is_frame_owner(fighter.module_accessor) {
+            return;
+        }
+        let owner = WarkModule::get_module_accessor_from_entry_id(SUPER_FREEZE.owner());
+        let ended = if owner.is_null()
+        || StatusModule::status_kind(owner) == *FIGHTER_STATUS_KIND_DEAD {
+            SUPER_FREEZE.stop();
+            for id in 0..PLAYER_MAX {
+                let module_accessor = WarkModule::get_module_accessor_from_entry_id(id as i32);
+                if !module_accessor.is_null() {
+                    StopModule::cancel_hit_stop(module_accessor);
+                }
+            }
+            true
+        }
+        else {
+            SUPER_FREEZE.tick()
+        };
+        if ended
+        && SUPER_FREEZE.is_zoomed() {
+            macros::CAM_ZOOM_OUT(fighter);
+        }
+    }
+
+    /// Sets how well this fighter plays when controlled by a CPU.
+    pub unsafe fn set_cpu_difficulty(fighter: &mut L2CFighterCommon, difficulty: CpuDifficulty) {
+        if let Some(slot) = WarkModule::player_slot(&mut CPU_BRAINS, fighter.module_accessor) {
+            slot.difficulty = difficulty;
+        }
+    }
+
+    /// Sets the statuses a CPU cancels into, in order, whenever it gets a cancel window.
+    pub unsafe fn set_cpu_combo(fighter: &mut L2CFighterCommon, combo: Vec<i32>) {
+        if let Some(slot) = WarkModule::player_slot(&mut CPU_BRAINS, fighter.module_accessor) {
+            slot.combo = combo;
+            slot.reset_combo();
+        }
+    }
+
+    /// Seeds a CPU's decisions. The same seed always leads to the same choices in the same situations.
+    pub unsafe fn set_cpu_seed(fighter: &mut L2CFighterCommon, seed: u32) {
+        if let Some(slot) = WarkModule::player_slot(&mut CPU_BRAINS, fighter.module_accessor) {
+            slot.seed(seed);
+        }
+    }
+
+    /// Lets a CPU cancel into the next part of its combo when the condition (CANCEL_ON_HIT, CANCEL_ON_BLOCK, CANCEL_ON_WHIFF) is met.
+    /// The CPU waits out its reaction delay first, and may drop the combo depending on its difficulty.
+    /// The combo starts over once the current move can be cancelled normally.
+    /// Call this every frame of your attack statuses. Does nothing for human players.
+    pub unsafe fn cpu_combo_check(fighter: &mut L2CFighterCommon, condition: i32) -> L2CValue {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !WarkModule::is_operation_cpu(fighter.module_accessor) {
+            return false.into();
+        }
+        if CancelModule::is_enable_cancel(fighter.module_accessor) {
+            CPU_BRAINS[entry_id].reset_combo();
+            return false.into();
+        }
+        let in_window = is_cancel_condition(fighter, condition);
+        if let Some(status) = CPU_BRAINS[entry_id].combo_step(in_window) {
+            StatusModule::change_status_request_from_script(fighter.module_accessor, status, true);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Checks if any opponent within `range` units is in an attack status.
+    unsafe fn is_cpu_threatened(fighter: &mut L2CFighterCommon, range: f32) -> bool {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        let pos_x = PostureModule::pos_x(fighter.module_accessor);
+        for id in 0..PLAYER_MAX {
+            if id == entry_id {
+                continue;
+            }
+            let module_accessor = WarkModule::get_module_accessor_from_entry_id(id as i32);
+            if module_accessor.is_null()
+            || (PostureModule::pos_x(module_accessor) - pos_x).abs() > range {
+                continue;
+            }
+            let status = StatusModule::status_kind(module_accessor);
+            if get_ground_normal_mask(status) != 0
+            || [
+                *FIGHTER_STATUS_KIND_ATTACK_AIR,
+                *FIGHTER_STATUS_KIND_SPECIAL_N,
+                *FIGHTER_STATUS_KIND_SPECIAL_S,
+                *FIGHTER_STATUS_KIND_SPECIAL_HI,
+                *FIGHTER_STATUS_KIND_SPECIAL_LW
+            ].contains(&status) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// In Fighting Game Mode, makes a CPU block attacks from opponents within `range` units,
+    /// after its reaction delay and as often as its difficulty allows.
+    /// Call this every frame of the statuses the CPU can block from. Does nothing for human players.
+    pub unsafe fn cpu_guard_check(fighter: &mut L2CFighterCommon, range: f32) -> L2CValue {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_IS_FGC)
+        || !WarkModule::is_operation_cpu(fighter.module_accessor)
+        || fighter.global_table[SITUATION_KIND].get_i32() != *SITUATION_KIND_GROUND {
+            return false.into();
+        }
+        let threatened = is_cpu_threatened(fighter, range);
+        if CPU_BRAINS[entry_id].guard_step(threatened) {
+            StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_GUARD_ON, true);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// In Fighting Game Mode, sometimes makes a CPU go for one of the given statuses, such as a low, an overhead or a throw.
+    /// How often depends on the CPU's difficulty. Call this every frame of the statuses the CPU can mix up from.
+    /// Does nothing for human players.
+    pub unsafe fn cpu_mixup_check(fighter: &mut L2CFighterCommon, options: Vec<i32>) -> L2CValue {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_IS_FGC)
+        || !WarkModule::is_operation_cpu(fighter.module_accessor) {
+            return false.into();
+        }
+        if let Some(index) = CPU_BRAINS[entry_id].mixup_step(options.len()) {
+            CPU_BRAINS[entry_id].reset_combo();
+            StatusModule::change_status_request_from_script(fighter.module_accessor, options[index], true);
+            return true.into();
+        }
+        false.into()
+    }
+
+    /// Sets how the training dummy behaves.
+    pub unsafe fn set_training_settings(settings: TrainingSettings) {
+        TRAINING_SETTINGS = settings;
+    }
+
+    /// Gets how the training dummy behaves.
+    pub unsafe fn get_training_settings() -> TrainingSettings {
+        TRAINING_SETTINGS
+    }
+
+    /// Checks if a fighter is a training dummy, which is any CPU while FGC_TRAINING is on.
+    pub unsafe fn is_training_dummy(module_accessor: *mut BattleObjectModuleAccessor) -> bool {
+        FGC_TRAINING && WarkModule::is_operation_cpu(module_accessor)
+    }
+
+    /// Checks if hits on this fighter should always count as counter-hits because of the training settings.
+    pub unsafe fn is_training_counter_hit(module_accessor: *mut BattleObjectModuleAccessor) -> bool {
+        is_training_dummy(module_accessor) && TRAINING_SETTINGS.counter_hit
+    }
+
+    /// Lets a human player change the training dummy's settings while FGC_TRAINING is on.
+    /// Holding Shield and pressing Up Taunt cycles the guard mode, Down Taunt cycles the tech option,
+    /// Left Taunt cycles the reversal and Right Taunt toggles counter-hits.
+    /// Holding Shield and Special and pressing Up Taunt starts or stops recording into the reversal's recording slot
+    /// (or slot 0 if the reversal isn't a recording).
+    /// Call this once per frame.
+    pub unsafe fn training_menu_check(fighter: &mut L2CFighterCommon) {
+        if !FGC_TRAINING
+        || WarkModule::is_operation_cpu(fighter.module_accessor)
+        || !ControlModule::check_button_on(fighter.module_accessor, *CONTROL_PAD_BUTTON_GUARD) {
+            return;
+        }
+        if ControlModule::check_button_on(fighter.module_accessor, *CONTROL_PAD_BUTTON_SPECIAL) {
+            if ControlModule::check_button_trigger(fighter.module_accessor, *CONTROL_PAD_BUTTON_APPEAL_HI) {
+                if RECORDING_STATE.is_some() {
+                    RECORDING_STATE = None;
+                }
+                else {
+                    let slot = match TRAINING_SETTINGS.reversal {
+                        Reversal::Recording(slot) => slot,
+                        _ => 0
+                    };
+                    RECORDINGS[slot] = Recording::NEW;
+                    RECORDING_STATE = Some((slot, 0));
+                }
+            }
+            return;
+        }
+        let setting = if ControlModule::check_button_trigger(fighter.module_accessor, *CONTROL_PAD_BUTTON_APPEAL_HI) {
+            Some(TrainingSetting::Guard)
+        }
+        else if ControlModule::check_button_trigger(fighter.module_accessor, *CONTROL_PAD_BUTTON_APPEAL_LW) {
+            Some(TrainingSetting::Tech)
+        }
+        else if ControlModule::check_button_trigger(fighter.module_accessor, *CONTROL_PAD_BUTTON_APPEAL_S_L) {
+            Some(TrainingSetting::Reversal)
+        }
+        else if ControlModule::check_button_trigger(fighter.module_accessor, *CONTROL_PAD_BUTTON_APPEAL_S_R) {
+            Some(TrainingSetting::CounterHit)
+        }
+        else {
+            None
+        };
+        if let Some(setting) = setting {
+            TRAINING_SETTINGS.cycle(setting);
+        }
+    }
+
+    /// Records a human player's status changes while a recording is running. Call this once per frame.
+    /// Only common statuses are recorded, since fighter-specific status kinds would do something else when played back on the dummy.
+    pub unsafe fn training_record_main(fighter: &mut L2CFighterCommon) {
+        if !FGC_TRAINING
+        || WarkModule::is_operation_cpu(fighter.module_accessor) {
+            return;
+        }
+        if let Some((slot, frame)) = RECORDING_STATE {
+            let status = fighter.global_table[STATUS_KIND].get_i32();
+            let recording = &mut RECORDINGS[slot];
+            if status < *FIGHTER_STATUS_KIND_NUM
+            && recording.statuses.last().map(|recorded| recorded.status) != Some(status) {
+                recording.record(frame, status);
+            }
+            RECORDING_STATE = Some((slot, frame + 1));
+        }
+    }
+
+    /// Makes the training dummy block, tech and do its wake-up reversal according to the training settings.
+    /// Call this once per frame. Does nothing unless the fighter is a training dummy.
+    pub unsafe fn training_dummy_main(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !is_training_dummy(fighter.module_accessor) {
+            return;
+        }
+        let status = fighter.global_table[STATUS_KIND].get_i32();
+        let damaged = MiscModule::is_damage_check(fighter.module_accessor, false);
+        let dummy = &mut DUMMY_STATES[entry_id];
+
+        if dummy.is_playing_back() {
+            if damaged {
+                dummy.stop_playback();
+            }
+            else if let Some(next_status) = dummy.playback_step(&RECORDINGS) {
+                StatusModule::change_status_request_from_script(fighter.module_accessor, next_status, true);
+            }
+            return;
+        }
+
+        // Techs
+        let launched = [
+            *FIGHTER_STATUS_KIND_DAMAGE_FLY,
+            *FIGHTER_STATUS_KIND_DAMAGE_FLY_ROLL,
+            *FIGHTER_STATUS_KIND_DAMAGE_FLY_METEOR
+        ].contains(&status);
+        let can_tech = launched
+        && WorkModule::is_enable_transition_term(fighter.module_accessor, *FIGHTER_STATUS_TRANSITION_TERM_ID_PASSIVE)
+        && FighterUtil::is_touch_passive_ground(fighter.module_accessor, *GROUND_TOUCH_FLAG_DOWN as u32);
+        match dummy.tech_step(TRAINING_SETTINGS.tech, launched, can_tech) {
+            Some(TechChoice::InPlace) => {
+                StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_PASSIVE, true);
+                return;
+            },
+            Some(TechChoice::Roll) => {
+                StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_PASSIVE_FB, true);
+                return;
+            },
+            None => {}
+        }
+
+        // Wake-up Reversals
+        if (*FIGHTER_STATUS_KIND_DOWN..=*FIGHTER_STATUS_KIND_LAY_DOWN).contains(&status)
+        || [*FIGHTER_STATUS_KIND_PASSIVE, *FIGHTER_STATUS_KIND_PASSIVE_FB].contains(&status) {
+            dummy.waking_up = true;
+        }
+        else if dummy.waking_up && !damaged {
+            dummy.waking_up = false;
+            match TRAINING_SETTINGS.reversal {
+                Reversal::Status => {
+                    StatusModule::change_status_request_from_script(fighter.module_accessor, TRAINING_SETTINGS.reversal_status, true);
+                    return;
+                },
+                Reversal::Recording(slot) => {
+                    dummy.start_playback(slot);
+                    return;
+                },
+                Reversal::None => {}
+            }
+        }
+
+        // Guarding
+        let threatened = is_cpu_threatened(fighter, TRAINING_SETTINGS.guard_range);
+        if dummy.guard_step(TRAINING_SETTINGS.guard, threatened, damaged)
+        && fighter.global_table[SITUATION_KIND].get_i32() == *SITUATION_KIND_GROUND
+        && [
+            *FIGHTER_STATUS_KIND_WAIT,
+            *FIGHTER_STATUS_KIND_WALK,
+            *FIGHTER_STATUS_KIND_SQUAT,
+            *FIGHTER_STATUS_KIND_SQUAT_WAIT,
+            *FIGHTER_STATUS_KIND_SQUAT_RV,
+            *FIGHTER_STATUS_KIND_TURN,
+            *FIGHTER_STATUS_KIND_DASH,
+            *FIGHTER_STATUS_KIND_LANDING,
+            *FIGHTER_STATUS_KIND_GUARD_OFF
+        ].contains(&status) {
+            StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_GUARD_ON, true);
+        }
+    }
+
+    /// The names used for each button on the input display.
+    unsafe fn input_display_button_names() -> [(i32, &'static str); 5] {
+        [
+            (1 << *CONTROL_PAD_BUTTON_ATTACK, "A"),
+            (1 << *CONTROL_PAD_BUTTON_SPECIAL, "B"),
+            (1 << *CONTROL_PAD_BUTTON_JUMP, "X"),
+            (1 << *CONTROL_PAD_BUTTON_GUARD, "R"),
+            (1 << *CONTROL_PAD_BUTTON_CATCH, "Z")
+        ]
+    }
+
+    /// Sets how the input display is drawn.
+    pub unsafe fn set_input_display_config(config: InputDisplayConfig) {
+        INPUT_DISPLAY_CONFIG = config;
+    }
+
+    /// Adds this frame's direction (as if facing right) and held buttons to the fighter's input display.
+    /// While FGC_TRAINING is on, the display is redrawn above the fighter whenever it changes (see render_input_display).
+    /// Call this once per frame.
+    pub unsafe fn update_input_display(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return;
+        }
+        let direction = get_command_stick_direction(fighter, true);
+        let buttons = ControlModule::get_button(fighter.module_accessor);
+        if INPUT_LOGS[entry_id].push(direction, buttons)
+        && FGC_TRAINING {
+            render_input_display(fighter);
+        }
+    }
+
+    /// Draws the fighter's newest inputs above them with the effects set in the InputDisplayConfig,
+    /// replacing whatever was drawn before.
+    pub unsafe fn render_input_display(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return;
+        }
+        let config = INPUT_DISPLAY_CONFIG;
+        for effect in config.effects() {
+            EffectModule::kill_kind(fighter.module_accessor, Hash40::new_raw(effect), false, true);
+        }
+        for sprite in layout_input_display(INPUT_LOGS[entry_id].entries(), &config) {
+            macros::EFFECT_FOLLOW(fighter, Hash40::new_raw(sprite.effect), Hash40::new("top"), sprite.x, sprite.y, 0, 0, 0, sprite.rotation, config.scale, true);
+        }
+    }
+
+    /// Returns the fighter's input display as lines of text like "6 A+B 12", newest first.
+    pub unsafe fn get_input_display_lines(fighter: &mut L2CFighterCommon) -> Vec<String> {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return Vec::new();
+        }
+        let names = input_display_button_names();
+        INPUT_LOGS[entry_id].entries().iter()
+            .rev()
+            .map(|entry| entry.label(&names))
+            .collect()
+    }
+
+    /// Empties the fighter's input display and removes it from the screen.
+    pub unsafe fn clear_input_display(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id < PLAYER_MAX {
+            INPUT_LOGS[entry_id].clear();
+        }
+        for effect in INPUT_DISPLAY_CONFIG.effects() {
+            EffectModule::kill_kind(fighter.module_accessor, Hash40::new_raw(effect), false, true);
+        }
+    }
+
+    /// Checks if a status is an attack, for the purposes of counter-hits and punish-counters.
+    pub unsafe fn is_attack_status(status_kind: i32) -> bool {
+        get_ground_normal_mask(status_kind) != 0
+        || [
+            *FIGHTER_STATUS_KIND_ATTACK_AIR,
+            *FIGHTER_STATUS_KIND_SPECIAL_N,
+            *FIGHTER_STATUS_KIND_SPECIAL_S,
+            *FIGHTER_STATUS_KIND_SPECIAL_HI,
+            *FIGHTER_STATUS_KIND_SPECIAL_LW,
+            *FIGHTER_STATUS_KIND_CATCH,
+            *FIGHTER_STATUS_KIND_CATCH_DASH,
+            *FIGHTER_STATUS_KIND_CATCH_TURN
+        ].contains(&status_kind)
+    }
+
+    /// Adds a function that gets called whenever a counter-hit or punish-counter lands,
+    /// so it can add hitstun, damage or effects.
+    /// Returns false if HIT_KIND_LISTENER_MAX listeners have already been added.
+    pub unsafe fn add_hit_kind_listener(listener: HitKindListener) -> bool {
+        add_listener(&mut HIT_KIND_LISTENERS, listener)
+    }
+
+    /// Returns how the last hit on this fighter was classified.
+    pub unsafe fn get_last_hit_kind(fighter: &mut L2CFighterCommon) -> HitKind {
+        WarkModule::player_slot(&mut LAST_HIT_KINDS, fighter.module_accessor).copied().unwrap_or(HitKind::Normal)
+    }
+
+    /// Gets the module accessor of the fighter a battle object belongs to. Weapons belong to the fighter that owns them.
+    /// Returns a null pointer if the object isn't a fighter or a fighter's weapon.
+    unsafe fn get_owner_fighter(object_id: u32) -> *mut BattleObjectModuleAccessor {
+        if !sv_battle_object::is_active(object_id) {
+            return std::ptr::null_mut();
+        }
+        let mut module_accessor = sv_battle_object::module_accessor(object_id);
+        if utility::get_category(&mut *module_accessor) == *BATTLE_OBJECT_CATEGORY_WEAPON {
+            let owner_id = WorkModule::get_int(module_accessor, *WEAPON_INSTANCE_WORK_ID_INT_LINK_OWNER) as u32;
+            if !sv_battle_object::is_active(owner_id) {
+                return std::ptr::null_mut();
+            }
+            module_accessor = sv_battle_object::module_accessor(owner_id);
+        }
+        if utility::get_category(&mut *module_accessor) != *BATTLE_OBJECT_CATEGORY_FIGHTER {
+            return std::ptr::null_mut();
+        }
+        module_accessor
+    }
+
+    /// Remembers who hit a fighter, so counter-hits and stun changes are applied for the right attacker.
+    /// Call this from a hook on the game's collision hit log event, with the object IDs of the attacker and the defender.
+    /// Hits from weapons count as hits from the fighter that owns them. Hits from anything else (items, stage hazards,
+    /// self hits) are recorded as having no attacker.
+    pub unsafe fn record_collision_hit(attacker_object_id: u32, defender_object_id: u32) {
+        let defender = get_owner_fighter(defender_object_id);
+        if defender.is_null() {
+            return;
+        }
+        let defender_id = WarkModule::get_entry_id(defender);
+        if defender_id >= PLAYER_MAX {
+            return;
+        }
+        let attacker = get_owner_fighter(attacker_object_id);
+        let attacker_id = if attacker.is_null() { PLAYER_MAX } else { WarkModule::get_entry_id(attacker) };
+        let record = if attacker_id < PLAYER_MAX && attacker_id != defender_id {
+            Some(HitRecord {
+                attacker: attacker_id,
+                motion: MotionModule::motion_kind(attacker)
+            })
+        }
+        else {
+            None
+        };
+        HIT_LOGS[defender_id].record(record);
+    }
+
+    /// Returns who hit the fighter, as recorded by record_collision_hit. Hits are forgotten once the stun from them ends.
+    pub unsafe fn get_last_attacker(module_accessor: *mut BattleObjectModuleAccessor) -> Option<HitRecord> {
+        let entry_id = WarkModule::get_entry_id(module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return None;
+        }
+        HIT_LOGS[entry_id].last_hit()
+    }
+
+    /// Tracks which part of an attack the fighter is in, and classifies hits on them as normal hits,
+    /// counter-hits (hit during startup) or punish-counters (hit during recovery).
+    /// Counter-hits and punish-counters are sent to every listener added with add_hit_kind_listener,
+    /// along with the attacker recorded by record_collision_hit. This is also what forgets a recorded hit once the stun from it ends.
+    /// Call this once per frame for every fighter.
+    pub unsafe fn hit_kind_main(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return;
+        }
+        let status = fighter.global_table[STATUS_KIND].get_i32();
+        let attack_active = (0..8).any(|id| AttackModule::is_attack(fighter.module_accessor, id, false));
+        let damaged = MiscModule::is_damage_check(fighter.module_accessor, false);
+        HIT_LOGS[entry_id].update(damaged || status == *FIGHTER_STATUS_KIND_GUARD_DAMAGE);
+        if let Some(mut kind) = PHASE_TRACKERS[entry_id].update(status, is_attack_status(status), attack_active, damaged) {
+            if kind == HitKind::Normal
+            && is_training_counter_hit(fighter.module_accessor) {
+                kind = HitKind::CounterHit;
+            }
+            LAST_HIT_KINDS[entry_id] = kind;
+            if kind != HitKind::Normal {
+                let attacker = HIT_LOGS[entry_id].last_hit().map(|record| record.attacker);
+                for listener in HIT_KIND_LISTENERS.iter().flatten() {
+                    listener(entry_id, attacker, kind);
+                }
+            }
+        }
+    }
+
+    /// Sets how this fighter's moves change hitstun and shieldstun in Fighting Game Mode.
+    pub unsafe fn set_stun_table(fighter: &mut L2CFighterCommon, table: StunTable) {
+        if let Some(slot) = WarkModule::player_slot(&mut STUN_TABLES, fighter.module_accessor) {
+            *slot = table;
+        }
+    }
+
+    /// Sets how one of this fighter's moves changes hitstun and shieldstun in Fighting Game Mode.
+    /// # Arguments
+    ///
+    /// * `motion` - The motion kind of the move, such as hash40("attack_11").
+    pub unsafe fn set_move_stun(fighter: &mut L2CFighterCommon, motion: u64, config: StunConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut STUN_TABLES, fighter.module_accessor) {
+            slot.set_move(motion, config);
+        }
+    }
+
+    /// Returns how many frames of hitstun or shieldstun the fighter has left, or 0 if they aren't in stun.
+    pub unsafe fn get_stun_frames_remaining(module_accessor: *mut BattleObjectModuleAccessor) -> i32 {
+        if StatusModule::status_kind(module_accessor) == *FIGHTER_STATUS_KIND_GUARD_DAMAGE {
+            return WorkModule::get_int(module_accessor, *FIGHTER_STATUS_GUARD_DAMAGE_WORK_INT_STIFF_FRAME).max(0);
+        }
+        if MiscModule::is_damage_check(module_accessor, false) {
+            return WorkModule::get_float(module_accessor, *FIGHTER_INSTANCE_WORK_ID_FLOAT_DAMAGE_REACTION_FRAME).max(0.0).ceil() as i32;
+        }
+        0
+    }
+
+    /// Sets how many frames of hitstun or shieldstun the fighter has left. Does nothing if they aren't in stun.
+    pub unsafe fn set_stun_frames_remaining(module_accessor: *mut BattleObjectModuleAccessor, frames: i32) {
+        let entry_id = WarkModule::get_entry_id(module_accessor);
+        let frames = frames.max(0);
+        if StatusModule::status_kind(module_accessor) == *FIGHTER_STATUS_KIND_GUARD_DAMAGE {
+            WorkModule::set_int(module_accessor, frames, *FIGHTER_STATUS_GUARD_DAMAGE_WORK_INT_STIFF_FRAME);
+        }
+        else if MiscModule::is_damage_check(module_accessor, false) {
+            WorkModule::set_float(module_accessor, frames as f32, *FIGHTER_INSTANCE_WORK_ID_FLOAT_DAMAGE_REACTION_FRAME);
+        }
+        else {
+            return;
+        }
+        if entry_id < PLAYER_MAX {
+            STUN_TRACKERS[entry_id].set(frames as f32);
+        }
+    }
+
+    /// Adds frames to the fighter's remaining hitstun or shieldstun. Use a negative amount to take frames away.
+    pub unsafe fn add_stun_frames(module_accessor: *mut BattleObjectModuleAccessor, frames: i32) {
+        let remaining = get_stun_frames_remaining(module_accessor);
+        set_stun_frames_remaining(module_accessor, remaining + frames);
+    }
+
+    /// In Fighting Game Mode, changes the hitstun or shieldstun from each new hit or block
+    /// according to the StunTable of the attacker recorded by record_collision_hit (see set_stun_table).
+    /// Only hits recorded since the last change are used, so a new stun is never changed by an older attacker's table.
+    /// Call this once per frame for every fighter.
+    pub unsafe fn stun_main(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_IS_FGC) {
+            return;
+        }
+        let blocking = fighter.global_table[STATUS_KIND].get_i32() == *FIGHTER_STATUS_KIND_GUARD_DAMAGE;
+        let stun = get_stun_frames_remaining(fighter.module_accessor) as f32;
+        if !STUN_TRACKERS[entry_id].is_new_stun(stun) {
+            return;
+        }
+        if let Some(record) = HIT_LOGS[entry_id].take(HitUse::Stun) {
+            let config = STUN_TABLES[record.attacker].for_motion(record.motion);
+            let modifier = if blocking { config.shieldstun } else { config.hitstun };
+            if modifier != StunModifier::Default {
+                set_stun_frames_remaining(fighter.module_accessor, modifier.apply(stun).round() as i32);
+            }
+        }
+    }
+
+    /// Sets how this fighter's player breaks throws in Fighting Game Mode.
+    pub unsafe fn set_throw_tech_config(fighter: &mut L2CFighterCommon, config: ThrowTechConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut THROW_TECH_CONFIGS, fighter.module_accessor) {
+            *slot = config;
+        }
+    }
+
+    /// Gets how this fighter's player breaks throws in Fighting Game Mode.
+    pub unsafe fn get_throw_tech_config(fighter: &mut L2CFighterCommon) -> ThrowTechConfig {
+        WarkModule::player_slot(&mut THROW_TECH_CONFIGS, fighter.module_accessor).copied().unwrap_or(ThrowTechConfig::DEFAULT)
+    }
+
+    /// Makes this fighter's grabs impossible to break, such as for command grabs. Only has an effect in Fighting Game Mode.
+    pub unsafe fn set_unblockable_throw(fighter: &mut L2CFighterCommon, unblockable: bool) {
+        if unblockable {
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_UNBLOCKABLE_THROW);
+        }
+        else {
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_UNBLOCKABLE_THROW);
+        }
+    }
+
+    /// In Fighting Game Mode, lets a grabbed fighter break the throw by pressing grab shortly after being grabbed.
+    /// Both fighters are released and pushed away from each other. Grabs from anything other than a fighter can't be broken.
+    /// Call this once per frame for every fighter.
+    pub unsafe fn throw_tech_main(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_IS_FGC) {
+            return;
+        }
+        let status = fighter.global_table[STATUS_KIND].get_i32();
+
+        // Pushback from a throw break gets applied once both fighters are in their release statuses.
+        let pushback = WorkModule::get_float(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_THROW_TECH_PUSHBACK);
+        if pushback != 0.0
+        && [*FIGHTER_STATUS_KIND_CAPTURE_CUT, *FIGHTER_STATUS_KIND_CATCH_CUT].contains(&status) {
+            macros::SET_SPEED_EX(fighter, -pushback, 0.0, *KINETIC_ENERGY_RESERVE_ATTRIBUTE_MAIN);
+            WarkModule::reset_f32(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLOAT_THROW_TECH_PUSHBACK);
+        }
+
+        let grabbed = [
+            *FIGHTER_STATUS_KIND_CAPTURE_PULLED,
+            *FIGHTER_STATUS_KIND_CAPTURE_WAIT,
+            *FIGHTER_STATUS_KIND_CAPTURE_DAMAGE
+        ].contains(&status);
+        let grab_pressed = ControlModule::check_button_trigger(fighter.module_accessor, *CONTROL_PAD_BUTTON_CATCH)
+            || (ControlModule::check_button_trigger(fighter.module_accessor, *CONTROL_PAD_BUTTON_ATTACK)
+            && ControlModule::check_button_on(fighter.module_accessor, *CONTROL_PAD_BUTTON_GUARD));
+        let config = THROW_TECH_CONFIGS[entry_id];
+        if !THROW_TECH_STATES[entry_id].update(&config, grabbed, grab_pressed) {
+            return;
+        }
+        let grabber_id = LinkModule::get_parent_object_id(fighter.module_accessor, *LINK_NO_CAPTURE) as u32;
+        if !sv_battle_object::is_active(grabber_id) {
+            return;
+        }
+        let grabber = sv_battle_object::module_accessor(grabber_id);
+        if grabber.is_null()
+        || utility::get_category(&mut *grabber) != *BATTLE_OBJECT_CATEGORY_FIGHTER
+        || WorkModule::is_flag(grabber, FIGHTER_INSTANCE_WORK_ID_FLAG_UNBLOCKABLE_THROW) {
+            return;
+        }
+        WorkModule::set_float(fighter.module_accessor, config.pushback, FIGHTER_INSTANCE_WORK_ID_FLOAT_THROW_TECH_PUSHBACK);
+        WorkModule::set_float(grabber, config.pushback, FIGHTER_INSTANCE_WORK_ID_FLOAT_THROW_TECH_PUSHBACK);
+        StatusModule::change_status_request_from_script(grabber, *FIGHTER_STATUS_KIND_CATCH_CUT, false);
+        StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_CAPTURE_CUT, false);
+    }
+
+    /// Sets the pushback this fighter's attacks cause in Fighting Game Mode.
+    pub unsafe fn set_pushback_config(fighter: &mut L2CFighterCommon, config: PushbackConfig) {
+        if let Some(slot) = WarkModule::player_slot(&mut PUSHBACK_CONFIGS, fighter.module_accessor) {
+            *slot = config;
+        }
+    }
+
+    /// Gets the pushback this fighter's attacks cause in Fighting Game Mode.
+    pub unsafe fn get_pushback_config(fighter: &mut L2CFighterCommon) -> PushbackConfig {
+        WarkModule::player_slot(&mut PUSHBACK_CONFIGS, fighter.module_accessor).copied().unwrap_or(PushbackConfig::DISABLED)
+    }
+
+    /// Checks if a fighter is in the corner: against a wall, or within `distance` of a ledge on the given side
+    /// (1.0 for the right, -1.0 for the left), no matter which way they're facing.
+    pub unsafe fn is_cornered(module_accessor: *mut BattleObjectModuleAccessor, side: f32, distance: f32) -> bool {
+        let wall_flag = if side > 0.0 { *GROUND_TOUCH_FLAG_RIGHT_SIDE } else { *GROUND_TOUCH_FLAG_LEFT_SIDE };
+        if GroundModule::is_wall_touch_line(module_accessor, wall_flag as u32) {
+            return true;
+        }
+        StatusModule::situation_kind(module_accessor) == *SITUATION_KIND_GROUND
+        && GroundModule::is_ottotto_lr(module_accessor, side, distance)
+    }
+
+    /// In Fighting Game Mode, pushes the victim away when this fighter's attacks hit or are blocked.
+    /// The victim is whoever record_collision_hit last recorded this fighter as hitting.
+    /// If the victim is in the corner, the attacker gets pushed back instead.
+    /// Also moves this fighter by any pushback they've been given. Call this once per frame for every fighter.
+    pub unsafe fn pushback_main(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !WorkModule::is_flag(fighter.module_accessor, FIGHTER_INSTANCE_WORK_ID_FLAG_IS_FGC) {
+            return;
+        }
+        let config = PUSHBACK_CONFIGS[entry_id];
+        let hit = AttackModule::is_infliction(fighter.module_accessor, *COLLISION_KIND_MASK_HIT);
+        let blocked = AttackModule::is_infliction(fighter.module_accessor, *COLLISION_KIND_MASK_SHIELD);
+        if config.enabled && (hit || blocked) {
+            let victim = take_victim(&mut HIT_LOGS, entry_id)
+                .map(|victim| (victim, WarkModule::get_module_accessor_from_entry_id(victim as i32)))
+                .filter(|(_, module_accessor)| !module_accessor.is_null());
+            if let Some((victim, victim_module_accessor)) = victim {
+                let amount = if hit { config.hit } else { config.block };
+                let diff = PostureModule::pos_x(victim_module_accessor) - PostureModule::pos_x(fighter.module_accessor);
+                let side = if diff != 0.0 { diff.signum() } else { PostureModule::lr(fighter.module_accessor) };
+                let (victim_push, attacker_push) = split_pushback(amount, is_cornered(victim_module_accessor, side, amount));
+                if victim_push > 0.0 {
+                    PUSHBACK_STATES[victim].start(victim_push * side, config.frames);
+                }
+                if attacker_push > 0.0 {
+                    PUSHBACK_STATES[entry_id].start(-attacker_push * side, config.frames);
+                }
+            }
+        }
+        let next_step = PUSHBACK_STATES[entry_id].next_step();
+        if next_step != 0.0
+        && !fighter.global_table[IN_HITLAG].get_bool() {
+            // Stops the push once it reaches a wall or ledge, so nobody gets pushed through a wall or off the stage.
+            let blocked = is_cornered(fighter.module_accessor, next_step.signum(), next_step.abs());
+            let distance = PUSHBACK_STATES[entry_id].step(blocked);
+            if distance != 0.0 {
+                PostureModule::add_pos(fighter.module_accessor, &Vector3f { x: distance, y: 0.0, z: 0.0 });
+            }
+        }
+    }
+
+    /// Handles adding or subtracting meter.
+    pub unsafe fn update_meter(object: *mut BattleObject, amount: f32, meter_max: f32, meter_const: i32) {
+        let module_accessor = (*object).module_accessor;
+        let mut meter = WorkModule::get_float(module_accessor, meter_const);
+        meter += amount;
         if meter < 0.0 {
             meter == 0.0;
         }
@@ -425,6 +2106,40 @@ pub mod FGCModule {
         }
         WorkModule::set_float(module_accessor, meter, meter_const);
     }
+
+    /// Measures the frame data of the fighter's current move and adds it to FRAME_DATA_TABLE once the move finishes.
+    /// Active frames are frames where any hitbox is out, and the move finishes once it can be cancelled normally or its motion ends.
+    /// Call this every frame of the statuses you want to measure.
+    pub unsafe fn frame_data_main(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || fighter.global_table[IN_HITLAG].get_bool() {
+            return;
+        }
+        let fighter_kind = fighter.global_table[FIGHTER_KIND].get_i32();
+        let motion = MotionModule::motion_kind(fighter.module_accessor);
+        let attack_active = (0..8).any(|id| AttackModule::is_attack(fighter.module_accessor, id, false));
+        let can_act = CancelModule::is_enable_cancel(fighter.module_accessor)
+            || MotionModule::is_end(fighter.module_accessor);
+        if let Some(data) = FRAME_DATA_RECORDERS[entry_id].observe(fighter_kind, motion, attack_active, can_act) {
+            FRAME_DATA_TABLE.record(data);
+        }
+    }
+
+    /// Gets the measured frame data of a move, if it's been measured this session.
+    pub unsafe fn get_frame_data(fighter_kind: i32, motion: u64) -> Option<FrameData> {
+        FRAME_DATA_TABLE.get(fighter_kind, motion).copied()
+    }
+
+    /// Writes every move measured this session out as CSV.
+    pub unsafe fn export_frame_data() -> String {
+        FRAME_DATA_TABLE.export()
+    }
+
+    /// Writes every move measured this session to a CSV file at the given path, like "sd:/frame_data.csv".
+    pub unsafe fn save_frame_data(path: &str) -> std::io::Result<()> {
+        std::fs::write(path, export_frame_data())
+    }
 }
 
 #[allow(non_snake_case)]
@@ -501,40 +2216,110 @@ pub mod MiscModule {
         false
     }
 
-    /// Forces a wall jump. Will be moved to FGCModule eventually.
+    /// Forces a wall jump. Kept so older scripts keep working, use FGCModule::wall_jump_check instead.
     pub unsafe fn wall_jump_check(fighter: &mut L2CFighterCommon) {
-        let is_right = GroundModule::is_wall_touch_line(fighter.module_accessor, *GROUND_TOUCH_FLAG_RIGHT_SIDE as u32);
-        let is_left = GroundModule::is_wall_touch_line(fighter.module_accessor, *GROUND_TOUCH_FLAG_LEFT_SIDE as u32);
-        let lr = PostureModule::lr(fighter.module_accessor);
-        if is_right {
-            let cat1 = fighter.global_table[CMD_CAT1].get_i32();
-            let dash = if lr < 0.0 { *FIGHTER_PAD_CMD_CAT1_FLAG_DASH } else { *FIGHTER_PAD_CMD_CAT1_FLAG_TURN_DASH };
-            if cat1 & (dash | *FIGHTER_PAD_CMD_CAT1_FLAG_JUMP_BUTTON) != 0 {
-                StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_WALL_JUMP, true);
-                return;
-            }
+        FGCModule::wall_jump_check(fighter);
+    }
+
+    /// Creates the "critical hit" effect. This is played as a cinematic, so it gets torn down after `frames`
+    /// or as soon as it gets interrupted, as long as cinematic_main is being called.
+    pub unsafe fn critical_zoom(fighter: &mut L2CFighterCommon, rate: u8, frames: f32, zoom: f32) {
+        if is_cinematic_playing(fighter) {
+            return;
         }
-        if is_left {
-            let cat1 = fighter.global_table[CMD_CAT1].get_i32();
-            let dash = if lr < 0.0 { *FIGHTER_PAD_CMD_CAT1_FLAG_TURN_DASH } else { *FIGHTER_PAD_CMD_CAT1_FLAG_DASH };
-            if cat1 & (dash | *FIGHTER_PAD_CMD_CAT1_FLAG_JUMP_BUTTON) != 0 {
-                StatusModule::change_status_request_from_script(fighter.module_accessor, *FIGHTER_STATUS_KIND_WALL_JUMP, true);
-                return;
-            }
+        let mut events = [
+            CinematicEvent { frame: 0, action: CinematicAction::Effect { effect: smash::hash40("sys_bg_criticalhit"), bone: smash::hash40("top"), scale: 1.0 } },
+            CinematicEvent { frame: 0, action: CinematicAction::Zoom { frames: frames, zoom: zoom } },
+            CinematicEvent { frame: 0, action: CinematicAction::Sound { sound: smash::hash40("se_common_criticalhit") } }
+        ].to_vec();
+        if rate != 0 {
+            events.push(CinematicEvent { frame: 0, action: CinematicAction::Slow { rate: rate, frames: 0 } });
         }
+        play_cinematic(fighter, Cinematic { events: events, length: frames as i32 });
     }
 
-    /// Creates the "critical hit" effect. Will be replaced later with a better implementation.
-    pub unsafe fn critical_zoom(fighter: &mut L2CFighterCommon, rate: u8, frames: f32, zoom: f32) {
-        if !SoundModule::is_playing(fighter.module_accessor, Hash40::new("se_common_finishhit")) {
-            macros::EFFECT(fighter, Hash40::new("sys_bg_criticalhit"), Hash40::new("top"), 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, false);
-            if rate != 0 {
-                SlowModule::set_whole(fighter.module_accessor, rate, 0);
-            }
-            if FighterUtil::get_opponent_fighter_num(fighter.module_accessor, true) < 2 {
-                macros:: CAM_ZOOM_IN_arg5(fighter, frames, 0.0, zoom, 0.0, 0.0);
+    /// Checks if a cinematic is playing for this fighter.
+    pub unsafe fn is_cinematic_playing(fighter: &mut L2CFighterCommon) -> bool {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        entry_id < PLAYER_MAX && CINEMATICS[entry_id].is_playing()
+    }
+
+    /// Starts a cinematic for this fighter, stopping any cinematic they already had playing.
+    /// The cinematic's first frame is played right away.
+    pub unsafe fn play_cinematic(fighter: &mut L2CFighterCommon, cinematic: Cinematic) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return;
+        }
+        if let Some(teardown) = CINEMATICS[entry_id].start(cinematic) {
+            cinematic_teardown(fighter, teardown);
+        }
+        cinematic_main(fighter);
+    }
+
+    /// Stops this fighter's cinematic, undoing any zoom, slowdown and effects it left behind.
+    pub unsafe fn stop_cinematic(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return;
+        }
+        if let Some(teardown) = CINEMATICS[entry_id].stop() {
+            cinematic_teardown(fighter, teardown);
+        }
+    }
+
+    unsafe fn cinematic_teardown(fighter: &mut L2CFighterCommon, teardown: CinematicTeardown) {
+        if teardown.zoom_out {
+            macros::CAM_ZOOM_OUT(fighter);
+        }
+        if teardown.slow_clear {
+            SlowModule::clear_whole(fighter.module_accessor);
+        }
+        for effect in teardown.effects.iter() {
+            EffectModule::kill_kind(fighter.module_accessor, Hash40::new_raw(*effect), false, true);
+        }
+    }
+
+    /// Plays this fighter's cinematic. Call this once per frame.
+    /// The cinematic gets torn down once it ends, or early if the fighter gets hit, gets KO'd, or the match ends.
+    /// Camera actions are skipped when there are more than two players.
+    pub unsafe fn cinematic_main(fighter: &mut L2CFighterCommon) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || !CINEMATICS[entry_id].is_playing() {
+            return;
+        }
+        let interrupts = CinematicInterrupts {
+            damaged: is_damage_check(fighter.module_accessor, false),
+            dead: fighter.global_table[STATUS_KIND].get_i32() == *FIGHTER_STATUS_KIND_DEAD,
+            not_ready_go: !sv_information::is_ready_go()
+        };
+        let suppress_camera = FighterUtil::get_opponent_fighter_num(fighter.module_accessor, true) >= 2;
+        let frame = CINEMATICS[entry_id].update(interrupts, suppress_camera);
+        for action in frame.actions.iter() {
+            match *action {
+                CinematicAction::Zoom { frames, zoom } => {
+                    macros::CAM_ZOOM_IN_arg5(fighter, frames, 0.0, zoom, 0.0, 0.0);
+                },
+                CinematicAction::ZoomOut => {
+                    macros::CAM_ZOOM_OUT(fighter);
+                },
+                CinematicAction::Slow { rate, frames } => {
+                    SlowModule::set_whole(fighter.module_accessor, rate, frames);
+                },
+                CinematicAction::SlowClear => {
+                    SlowModule::clear_whole(fighter.module_accessor);
+                },
+                CinematicAction::Effect { effect, bone, scale } => {
+                    macros::EFFECT(fighter, Hash40::new_raw(effect), Hash40::new_raw(bone), 0, 0, 0, 0, 0, 0, scale, 0, 0, 0, 0, 0, 0, false);
+                },
+                CinematicAction::Sound { sound } => {
+                    macros::PLAY_SE(fighter, Hash40::new_raw(sound));
+                }
             }
-            macros::PLAY_SE(fighter, Hash40::new("se_common_criticalhit"));
+        }
+        if let Some(teardown) = frame.teardown {
+            cinematic_teardown(fighter, teardown);
         }
     }
 
@@ -568,4 +2353,219 @@ pub mod MiscModule {
             WorkModule::on_flag(module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_HOLD);
         }
     }
+
+    /// Lets a taunt be cancelled into another motion by pressing a second button.
+    pub unsafe fn set_appeal_action(module_accessor: *mut BattleObjectModuleAccessor, action_mot: u64, action_button: i32) {
+        WorkModule::set_int64(
+            module_accessor,
+            action_mot as i64,
+            FIGHTER_STATUS_APPEAL_WORK_INT_APPEAL_ACTION_MOT
+        );
+        WorkModule::set_int(module_accessor, action_button, FIGHTER_STATUS_APPEAL_WORK_INT_APPEAL_ACTION_BUTTON);
+        WorkModule::on_flag(module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_ENABLE_ACTION);
+    }
+
+    /// Sets the taunts for this fighter's player.
+    pub unsafe fn set_appeals(fighter: &mut L2CFighterCommon, appeals: Vec<AppealDef>) {
+        if let Some(slot) = WarkModule::player_slot(&mut APPEALS, fighter.module_accessor) {
+            *slot = appeals;
+        }
+    }
+
+    /// Returns the fighter's definition of the taunt they're currently doing, if there is one.
+    pub unsafe fn get_current_appeal(fighter: &mut L2CFighterCommon) -> Option<AppealDef> {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX {
+            return None;
+        }
+        let motion = MotionModule::motion_kind(fighter.module_accessor);
+        find_appeal(&APPEALS[entry_id], motion).copied()
+    }
+
+    /// Sets up the current taunt from the fighter's taunt definitions, replacing the need to call
+    /// set_appeal_loop and set_appeal_action by hand. Call this once the taunt's motion has been set.
+    pub unsafe fn appeal_init(fighter: &mut L2CFighterCommon) {
+        if let Some(appeal) = get_current_appeal(fighter) {
+            match appeal.kind {
+                AppealKind::Hold => set_appeal_loop(fighter.module_accessor, false, appeal.loop_mot, appeal.restart_frame, appeal.held_button),
+                AppealKind::Loop => set_appeal_loop(fighter.module_accessor, true, appeal.loop_mot, appeal.restart_frame, appeal.held_button),
+                AppealKind::Normal => {}
+            }
+            if appeal.action_mot != 0 {
+                set_appeal_action(fighter.module_accessor, appeal.action_mot, appeal.action_button);
+            }
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_BUFF_GIVEN);
+        }
+    }
+
+    /// Changes into the taunt's action motion if its action button is pressed. This also ends any hold or loop.
+    pub unsafe fn appeal_action_check(fighter: &mut L2CFighterCommon) -> bool {
+        if !WorkModule::is_flag(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_ENABLE_ACTION) {
+            return false;
+        }
+        let button = WorkModule::get_int(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_INT_APPEAL_ACTION_BUTTON);
+        if ControlModule::check_button_trigger(fighter.module_accessor, button) {
+            let action_mot = WorkModule::get_int64(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_INT_APPEAL_ACTION_MOT);
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_ENABLE_ACTION);
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_HOLD);
+            WorkModule::off_flag(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_LOOP);
+            MotionModule::change_motion(
+                fighter.module_accessor,
+                Hash40::new_raw(action_mot),
+                0.0,
+                1.0,
+                false,
+                0.0,
+                false,
+                false
+            );
+            return true;
+        }
+        false
+    }
+
+    /// Gives the taunt's buff once it reaches buff_frame. Each taunt only gives its buff once.
+    pub unsafe fn appeal_buff_check(fighter: &mut L2CFighterCommon) {
+        let given = WorkModule::is_flag(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_BUFF_GIVEN);
+        let frame = MotionModule::frame(fighter.module_accessor);
+        if let Some(buff) = get_current_appeal(fighter).and_then(|appeal| appeal.buff_due(frame, given)) {
+            match buff {
+                AppealBuff::Meter { amount, meter_max, meter_const } => {
+                    FGCModule::update_meter(fighter.battle_object, amount, meter_max, meter_const);
+                },
+                AppealBuff::Install { flag, timer_const, frames } => {
+                    WorkModule::on_flag(fighter.module_accessor, flag);
+                    WorkModule::set_float(fighter.module_accessor, frames, timer_const);
+                },
+                AppealBuff::None => {}
+            }
+            WorkModule::on_flag(fighter.module_accessor, FIGHTER_STATUS_APPEAL_WORK_FLAG_APPEAL_BUFF_GIVEN);
+        }
+    }
+
+    /// Runs taunt actions and buffs. Call this every frame of the taunt status.
+    pub unsafe fn appeal_main(fighter: &mut L2CFighterCommon) {
+        appeal_action_check(fighter);
+        appeal_buff_check(fighter);
+    }
+
+    /// Sets how many rounds a match lasts and how long the intro and KO slow motion are.
+    pub unsafe fn set_match_config(config: MatchConfig) {
+        MATCH_STATE.config = config;
+    }
+
+    /// Starts a new match from round 1 with every fighter currently in the game.
+    pub unsafe fn start_match() {
+        let mut active = [false; PLAYER_MAX];
+        for (id, active) in active.iter_mut().enumerate() {
+            *active = WarkModule::is_entry_active(id);
+        }
+        MATCH_STATE.start(active);
+    }
+
+    /// Stops the match and clears the score.
+    pub unsafe fn reset_match() {
+        MATCH_STATE.reset();
+    }
+
+    pub unsafe fn get_match_phase() -> MatchPhase {
+        MATCH_STATE.phase()
+    }
+
+    /// Adds a function that gets called for every match event, such as round starts, KOs and time overs.
+    /// Returns false if MATCH_EVENT_LISTENER_MAX listeners have already been added.
+    pub unsafe fn add_match_event_listener(listener: MatchEventListener) -> bool {
+        add_listener(&mut MATCH_EVENT_LISTENERS, listener)
+    }
+
+    /// Heals whatever damage the fighter took last round, then sets their HP with MiscModule::set_hp.
+    /// Like set_hp, this does nothing in training mode.
+    pub unsafe fn restore_hp(fighter: &mut L2CFighterCommon, hp: f32) {
+        if smashball::is_training_mode() {
+            return;
+        }
+        DamageModule::heal(fighter.module_accessor, -DamageModule::damage(fighter.module_accessor, 0), 0);
+        MiscModule::set_hp(fighter, hp);
+    }
+
+    /// Runs the match. Restores the fighter's HP (using restore_hp, which starts them on `hp` damage) at the start of each round,
+    /// and reports them when they're KO'd.
+    /// The frame owner (see WarkModule::is_frame_owner) also moves the match forward, slows the game down when a round is decided,
+    /// and sends events out to listeners. Call this once per frame for every fighter.
+    pub unsafe fn match_main(fighter: &mut L2CFighterCommon, hp: f32) {
+        let entry_id = WarkModule::get_entry_id(fighter.module_accessor);
+        if entry_id >= PLAYER_MAX
+        || MATCH_STATE.phase() == MatchPhase::Idle {
+            return;
+        }
+        if MATCH_STATE.needs_round_start(entry_id) {
+            restore_hp(fighter, hp);
+            HIT_LOGS[entry_id].clear();
+        }
+        if fighter.global_table[STATUS_KIND].get_i32() == *FIGHTER_STATUS_KIND_DEAD {
+            MATCH_STATE.ko(entry_id);
+        }
+        if !WarkModule::is_frame_owner(fighter.module_accessor) {
+            return;
+        }
+        MATCH_STATE.tick();
+        for event in MATCH_STATE.take_events().iter() {
+            match event {
+                MatchEvent::PlayerKo { .. } | MatchEvent::TimeOver => {
+                    if MATCH_STATE.phase() == MatchPhase::RoundKo {
+                        SlowModule::set_whole(fighter.module_accessor, 4, MATCH_STATE.config.ko_frames);
+                    }
+                },
+                MatchEvent::RoundEnd { .. } => {
+                    SlowModule::clear_whole(fighter.module_accessor);
+                },
+                _ => {}
+            }
+            for listener in MATCH_EVENT_LISTENERS.iter().flatten() {
+                listener(event);
+            }
+        }
+    }
+
+    /// Returns the percentage (0.0 to 1.0) of HP a fighter has left. `hp` is the damage every fighter starts the round on,
+    /// as passed to match_main, and a fighter has no HP left once their damage reaches `max_hp` (see hp_percentage).
+    pub unsafe fn get_hp_percentage(module_accessor: *mut BattleObjectModuleAccessor, hp: f32, max_hp: f32) -> f32 {
+        hp_percentage(DamageModule::damage(module_accessor, 0), hp, max_hp)
+    }
+
+    /// Returns the time left in the round, in seconds.
+    pub unsafe fn get_round_seconds_left() -> i32 {
+        ROUND_TIMER.seconds_left()
+    }
+
+    /// Runs the round timer, using the time limit from the MatchConfig.
+    /// Time only counts down during the Fight phase, is affected by slowdown the same way WarkModule::count_down is,
+    /// and stops during cinematics and super freezes. When time runs out, the round goes to whoever has the highest
+    /// percentage of their HP left (see get_hp_percentage), or is a draw if the highest is tied.
+    /// Call this once per frame for every fighter. Only the frame owner (see WarkModule::is_frame_owner) runs the timer.
+    pub unsafe fn round_timer_main(fighter: &mut L2CFighterCommon, hp: f32, max_hp: f32) {
+        if !WarkModule::is_frame_owner(fighter.module_accessor) {
+            return;
+        }
+        let round = MATCH_STATE.round();
+        if ROUND_TIMER.round() != round {
+            ROUND_TIMER.start(MATCH_STATE.config.time_limit, round);
+        }
+        if !MATCH_STATE.is_fighting() {
+            return;
+        }
+        let paused = FGCModule::is_super_freeze()
+            || CINEMATICS.iter().any(|cinematic| cinematic.is_playing());
+        let frames = SlowModule::rate(fighter.module_accessor) * sv_information::slow_rate();
+        if ROUND_TIMER.tick(frames, paused) {
+            let mut hp_percentages = [None; PLAYER_MAX];
+            for (id, hp_percentage) in hp_percentages.iter_mut().enumerate() {
+                let module_accessor = WarkModule::get_module_accessor_from_entry_id(id as i32);
+                if !module_accessor.is_null() {
+                    *hp_percentage = Some(get_hp_percentage(module_accessor, hp, max_hp));
+                }
+            }
+            MATCH_STATE.time_over(resolve_time_over(&hp_percentages));
+        }
+    }
 }